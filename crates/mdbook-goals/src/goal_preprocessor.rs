@@ -12,8 +12,9 @@ use rust_project_goals::format_champions::format_champions;
 use rust_project_goals::format_team_ask::format_team_asks;
 use rust_project_goals::format_team_support::format_team_support;
 use rust_project_goals::markdown_processor::{MarkdownProcessor, MarkdownProcessorState};
+use rust_project_goals::progress_history::ProgressHistory;
 use rust_project_goals::util;
-use rust_project_goals_cli::Order;
+use rust_project_goals_cli::{GoalView, Order, UpdatesFormat};
 
 use rust_project_goals::spanned::Spanned;
 use rust_project_goals::{
@@ -86,6 +87,8 @@ impl<'c> GoalPreprocessorWithContext<'c> {
                 self.replace_goal_count(chapter)?;
                 self.replace_flagship_goal_count(chapter)?;
                 self.replace_reports(chapter)?;
+                self.replace_burn_up_charts(chapter)?;
+                self.replace_goal_renders(chapter)?;
                 // Process all markdown linking using shared processor
                 chapter.content = self
                     .markdown_processor
@@ -587,6 +590,117 @@ impl<'c> GoalPreprocessorWithContext<'c> {
         Ok(())
     }
 
+    /// Replace `(((BURN-UP CHART)))` with a chart of the progress of the milestone the chapter
+    /// is in, and `(((BURN-UP CHART: goal.md)))` with a chart of the progress of that goal,
+    /// drawn from the snapshots recorded by `cargo rpg snapshot-progress`.
+    fn replace_burn_up_charts(&mut self, chapter: &mut Chapter) -> anyhow::Result<()> {
+        loop {
+            let Some(captures) = re::BURN_UP_CHART.captures(&chapter.content) else {
+                return Ok(());
+            };
+            let range = captures.get(0).unwrap().range();
+            let goal_file = captures.get(1).map(|m| m.as_str().to_string());
+
+            let Some(chapter_path) = &chapter.path else {
+                anyhow::bail!("found `(((BURN-UP CHART)))` but chapter has no path")
+            };
+            let Some(milestone_path) = chapter_path.parent() else {
+                anyhow::bail!("cannot find the milestone of `{chapter_path:?}`")
+            };
+            let milestone = milestone_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default()
+                .to_string();
+
+            let history = ProgressHistory::load(&self.ctx.config.book.src.join(milestone_path))
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+
+            let chart = match goal_file {
+                None => history.burn_up_chart(&milestone, None),
+                Some(goal_file) => {
+                    let goals = self.goal_documents(chapter_path)?;
+                    let Some(goal) = find_goal(&goals, &goal_file) else {
+                        anyhow::bail!(
+                            "no goal `{goal_file}` in {milestone} for `(((BURN-UP CHART)))`"
+                        )
+                    };
+                    goal.metadata.tracking_issue.as_ref().and_then(|issue| {
+                        history.burn_up_chart(&goal.metadata.title, Some(issue.number))
+                    })
+                }
+            };
+
+            let output = chart.unwrap_or_else(|| {
+                "*No progress has been recorded yet (see `cargo rpg snapshot-progress`).*"
+                    .to_string()
+            });
+            chapter.content.replace_range(range, &output);
+        }
+    }
+
+    /// Replace `(((GOAL CARD: goal.md)))` with the card of that goal as in the monthly updates
+    /// (its progress, TL;DR, help wanted, and updates), and `(((GOAL COMMENTS: goal.md)))`
+    /// with only its updates, both over the last ~three months. The goal is named by its file
+    /// in the milestone of the chapter, or like `2025h2/goal.md` on pages outside of it.
+    fn replace_goal_renders(&mut self, chapter: &mut Chapter) -> anyhow::Result<()> {
+        for (regex, view) in [
+            (&*re::GOAL_CARD, GoalView::Card),
+            (&*re::GOAL_COMMENTS, GoalView::Comments),
+        ] {
+            while let Some(captures) = regex.captures(&chapter.content) {
+                let range = captures.get(0).unwrap().range();
+                let goal_file = captures[1].to_string();
+
+                let Some(chapter_path) = &chapter.path else {
+                    anyhow::bail!("found `{}` but chapter has no path", &captures[0])
+                };
+                let goal_path = if goal_file.contains('/') {
+                    PathBuf::from(&goal_file)
+                } else {
+                    chapter_path.with_file_name(&goal_file)
+                };
+                let milestone = goal_path
+                    .parent()
+                    .and_then(|path| path.file_name())
+                    .and_then(|name| name.to_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let name = goal_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or_default()
+                    .to_string();
+
+                let goals = self.goal_documents(&goal_path)?;
+                let Some(goal) = find_goal(&goals, &name) else {
+                    anyhow::bail!("no goal `{name}` in {milestone} for `{}`", &captures[0])
+                };
+
+                let output = match &goal.metadata.tracking_issue {
+                    None => "*This goal has no tracking issue yet.*".to_string(),
+                    Some(tracking_issue) => {
+                        let end_date = chrono::Utc::now().date_naive();
+                        let start_date = end_date - chrono::TimeDelta::days(90);
+                        let issues = self.get_or_load_milestone_issues(&milestone)?;
+                        rust_project_goals_cli::render_goal(
+                            &issues,
+                            &tracking_issue.repository,
+                            &milestone,
+                            tracking_issue.number,
+                            &Some(start_date),
+                            &None,
+                            view,
+                        )
+                        .map_err(|e| anyhow::anyhow!("failed to render `{goal_file}`: {e}"))?
+                    }
+                };
+                chapter.content.replace_range(range, &output);
+            }
+        }
+        Ok(())
+    }
+
     fn generate_report_chapters(
         &mut self,
         parent_chapter: &mut Chapter,
@@ -734,6 +848,7 @@ impl<'c> GoalPreprocessorWithContext<'c> {
             None,
             false,
             Order::OldestFirst,
+            UpdatesFormat::Blog,
         )
         .map_err(|e| anyhow::anyhow!("Failed to generate blog post content: {}", e))?;
 
@@ -771,6 +886,7 @@ impl<'c> GoalPreprocessorWithContext<'c> {
             Some(team_name),
             false,
             Order::NewestFirst,
+            UpdatesFormat::Blog,
         )
         .map_err(|e| anyhow::anyhow!("Failed to generate champion report content: {}", e))?;
 
@@ -856,6 +972,14 @@ impl<'c> GoalPreprocessorWithContext<'c> {
     }
 }
 
+/// The goal among `goals` whose file is `name`, with or without its `.md` extension.
+fn find_goal<'g>(goals: &'g [GoalDocument], name: &str) -> Option<&'g GoalDocument> {
+    goals.iter().find(|goal| {
+        goal.path.file_name().and_then(|file| file.to_str()) == Some(name)
+            || goal.path.file_stem().and_then(|stem| stem.to_str()) == Some(name)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,7 +373,7 @@ pub fn create_cfp(timeframe: &str, force: bool, dry_run: bool) -> Result<()> {
 }
 
 /// Validates that the timeframe is in the correct format (e.g., "2025h1" or "2025H1" or '2026')
-fn validate_timeframe(timeframe: &str) -> Result<()> {
+pub(crate) fn validate_timeframe(timeframe: &str) -> Result<()> {
     let re = Regex::new(MILESTONE_REGEX).unwrap();
     if !re.is_match(timeframe) {
         return Err(Error::str("Invalid timeframe format. Expected format: YYYYhN or YYYYHN (e.g., 2025h1, 2025H1, 2025h2, or 2025H2"));
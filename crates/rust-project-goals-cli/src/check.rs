@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use rust_project_goals::{
+    config::GoalsConfig,
+    gh::{
+        issue_id::Repository,
+        issues::{all_checkboxes, list_issues_in_milestone},
+    },
+    goal::GoalDocument,
+    lint::{self, LintFinding, LintLevel},
+    spanned::{self, Context as _, Error, Result},
+    util::{markdown_files, MILESTONE_REGEX},
+};
+use rust_project_goals_json::Progress;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+/// How `check` reports the problems it finds.
+#[derive(clap::ValueEnum, Copy, Clone, Debug, Default)]
+pub enum CheckFormat {
+    /// Human readable errors with source snippets.
+    #[default]
+    Human,
+    /// A JSON array of diagnostics.
+    Json,
+    /// GitHub Actions workflow commands, which show up as annotations on pull requests.
+    Github,
+}
+
+/// A problem found in a goal document.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+
+    /// The lint that found the problem, if it is not an error loading the goal.
+    pub lint: Option<&'static str>,
+
+    pub path: PathBuf,
+
+    /// 1-based line of the problem, if known.
+    pub line: Option<usize>,
+
+    /// 1-based column of the problem, if known.
+    pub column: Option<usize>,
+
+    /// For errors, the full error including the source snippets;
+    /// for lints, just the message.
+    pub message: String,
+
+    /// For lints, the line of the goal document the problem is on.
+    #[serde(skip)]
+    source_line: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticLevel {
+    Error,
+    Warning,
+}
+
+/// Checks every goal document in every milestone directory, reporting all the
+/// problems found (rather than stopping at the first one) in the given format.
+/// Fails at the end if there were any errors or denied lints.
+pub fn check(format: CheckFormat) -> Result<()> {
+    lint::check_lint_config()?;
+    let goals_config = GoalsConfig::from_book_toml("book.toml")
+        .map_err(|e| Error::str(format!("failed to load goals configuration: {e:?}")))?;
+
+    // Look for all directories like `2024h2`, `2025h1` or `2026` and load goals from those directories.
+    let regex = Regex::new(MILESTONE_REGEX)?;
+
+    let mut diagnostics = vec![];
+    let mut documents = 0;
+    for entry in WalkDir::new("src") {
+        let entry = entry?;
+
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        let Some(name) = entry.file_name().to_str() else {
+            continue;
+        };
+
+        if !regex.is_match(name) {
+            continue;
+        }
+
+        let files = match markdown_files(entry.path()) {
+            Ok(files) => files,
+            Err(e) => {
+                diagnostics.push(Diagnostic::new(entry.path(), &e));
+                continue;
+            }
+        };
+
+        for (path, link_path) in files {
+            // Skip template files
+            if path.file_name().unwrap() == "TEMPLATE.md" {
+                continue;
+            }
+
+            let goal_document = match GoalDocument::load(&path, &link_path) {
+                Ok(Some(goal_document)) => goal_document,
+                Ok(None) => continue,
+                Err(e) => {
+                    diagnostics.push(Diagnostic::new(&path, &e));
+                    continue;
+                }
+            };
+            documents += 1;
+
+            let text =
+                std::fs::read_to_string(&path).with_path_context(&path, "Failed to read goal")?;
+            for finding in lint::lint_goal(&goal_document, &text, &goals_config)? {
+                diagnostics.push(Diagnostic::from_lint(&path, &text, finding));
+            }
+        }
+    }
+
+    match format {
+        CheckFormat::Human => {
+            for diagnostic in &diagnostics {
+                eprintln!("{}\n", diagnostic.render());
+            }
+        }
+        CheckFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+        }
+        CheckFormat::Github => {
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostic.github_annotation());
+            }
+        }
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.level == DiagnosticLevel::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if errors > 0 {
+        spanned::bail_here!(
+            "found {errors} error(s) and {warnings} warning(s) in {documents} goal document(s)"
+        );
+    } else if warnings > 0 {
+        eprintln!("found {warnings} warning(s) in {documents} goal document(s)");
+    }
+
+    Ok(())
+}
+
+/// Computes the progress of every tracking issue in `milestone`, reporting each one whose
+/// `Tracked issues`, `Tracked PRs`, or `See also` references are broken (e.g., malformed, pointing at
+/// issues that do not exist, or forming a cycle). Fails at the end if there were any.
+pub fn check_progress(repository: &Repository, milestone: &str) -> Result<()> {
+    let issues = list_issues_in_milestone(repository, milestone)?;
+    let tracking_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.has_label("C-tracking-issue"))
+        .collect();
+
+    let mut broken = 0;
+    for (issue, progress) in tracking_issues
+        .iter()
+        .zip(all_checkboxes(tracking_issues.iter().copied()))
+    {
+        if let Progress::Error { message } = progress {
+            eprintln!("{repository}#{} ({}): {message}", issue.number, issue.title);
+            broken += 1;
+        }
+    }
+
+    if broken > 0 {
+        spanned::bail_here!(
+            "the progress of {broken} of {} tracking issue(s) in {milestone} could not be computed",
+            tracking_issues.len()
+        );
+    }
+    eprintln!(
+        "computed the progress of all {} tracking issue(s) in {milestone}",
+        tracking_issues.len()
+    );
+    Ok(())
+}
+
+impl Diagnostic {
+    fn new(path: &Path, error: &Error) -> Self {
+        let message = format!("{error:?}");
+        let (line, column) = location(path, &message);
+        Diagnostic {
+            level: DiagnosticLevel::Error,
+            lint: None,
+            path: path.to_path_buf(),
+            line,
+            column,
+            message,
+            source_line: None,
+        }
+    }
+
+    fn from_lint(path: &Path, text: &str, finding: LintFinding) -> Self {
+        let level = match finding.level {
+            LintLevel::Deny => DiagnosticLevel::Error,
+            LintLevel::Warn | LintLevel::Allow => DiagnosticLevel::Warning,
+        };
+
+        let (line, column, source_line) = match finding.bytes {
+            Some(bytes) => {
+                let line_start = text[..bytes.start].rfind('\n').map_or(0, |i| i + 1);
+                let line_end = text[bytes.start..]
+                    .find('\n')
+                    .map_or(text.len(), |i| bytes.start + i);
+                (
+                    Some(text[..bytes.start].matches('\n').count() + 1),
+                    Some(text[line_start..bytes.start].chars().count() + 1),
+                    Some(text[line_start..line_end].to_string()),
+                )
+            }
+            None => (None, None, None),
+        };
+
+        Diagnostic {
+            level,
+            lint: Some(finding.lint),
+            path: path.to_path_buf(),
+            line,
+            column,
+            message: finding.message,
+            source_line,
+        }
+    }
+
+    /// Format for humans. Errors already include their location and source snippets.
+    fn render(&self) -> String {
+        let Some(lint) = self.lint else {
+            // Errors without a span in the document do not name the file themselves.
+            return match self.line {
+                Some(_) => self.message.clone(),
+                None => format!("in {}:\n{}", self.path.display(), self.message),
+            };
+        };
+
+        let level = match self.level {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        };
+        let mut output = format!(
+            "{level}[{lint}]: {}\n --> {}",
+            self.message,
+            self.path.display()
+        );
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            output.push_str(&format!(":{line}:{column}"));
+        }
+        if let (Some(line), Some(source_line)) = (self.line, &self.source_line) {
+            output.push_str(&format!("\n{line:>4} | {source_line}"));
+        }
+        output
+    }
+
+    /// Format as a GitHub Actions `::error` or `::warning` workflow command.
+    fn github_annotation(&self) -> String {
+        let mut properties = format!("file={}", escape_property(&self.path.display().to_string()));
+        if let Some(line) = self.line {
+            properties.push_str(&format!(",line={line}"));
+        }
+        if let Some(column) = self.column {
+            properties.push_str(&format!(",col={column}"));
+        }
+        if let Some(lint) = self.lint {
+            properties.push_str(&format!(",title={}", escape_property(lint)));
+        }
+        let command = match self.level {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        };
+        format!("::{command} {properties}::{}", escape_data(&self.message))
+    }
+}
+
+/// `spanned::Error` does not expose its spans, but its rendering names the location
+/// of each span as `path:line:column`; the first one in `path` is the primary span.
+fn location(path: &Path, message: &str) -> (Option<usize>, Option<usize>) {
+    let regex = Regex::new(&format!(
+        r"{}:(\d+)(?::(\d+))?",
+        regex::escape(&path.display().to_string())
+    ))
+    .unwrap();
+
+    let Some(captures) = regex.captures(message) else {
+        return (None, None);
+    };
+    let number = |index| captures.get(index).and_then(|m| m.as_str().parse().ok());
+    (number(1), number(2))
+}
+
+/// See <https://github.com/actions/toolkit/blob/main/packages/core/src/command.ts>
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let path = Path::new("src/2025h2/foo.md");
+        assert_eq!(
+            location(path, "error: bad\n --> src/2025h2/foo.md:12:3\n"),
+            (Some(12), Some(3))
+        );
+        assert_eq!(
+            location(path, "error: bad\n --> src/2025h2/foo.md:12\n"),
+            (Some(12), None)
+        );
+        assert_eq!(location(path, "error: bad"), (None, None));
+    }
+
+    #[test]
+    fn test_github_annotation() {
+        let diagnostic = Diagnostic {
+            level: DiagnosticLevel::Error,
+            lint: None,
+            path: PathBuf::from("src/2025h2/foo.md"),
+            line: Some(4),
+            column: None,
+            message: "100% wrong\nsecond line".to_string(),
+            source_line: None,
+        };
+        assert_eq!(
+            diagnostic.github_annotation(),
+            "::error file=src/2025h2/foo.md,line=4::100%25 wrong%0Asecond line"
+        );
+    }
+
+    #[test]
+    fn test_lint_diagnostic() {
+        let text = "# Title\n\nSee [x][nowhere].\n";
+        let finding = LintFinding {
+            lint: "unresolved-link",
+            level: LintLevel::Warn,
+            message: "no definition".to_string(),
+            bytes: Some(13..25),
+        };
+        let diagnostic = Diagnostic::from_lint(Path::new("foo.md"), text, finding);
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(3), Some(5)));
+        assert_eq!(
+            diagnostic.render(),
+            "warning[unresolved-link]: no definition\n --> foo.md:3:5\n   3 | See [x][nowhere]."
+        );
+        assert_eq!(
+            diagnostic.github_annotation(),
+            "::warning file=foo.md,line=3,col=5,title=unresolved-link::no definition"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Atom feeds of the status updates posted on the tracking issues of a milestone,
+//! so that people can follow the goals they care about in a feed reader.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rust_project_goals::{
+    gh::{
+        issue_id::{IssueId, Repository},
+        issues::{list_issues_in_milestone, ExistingGithubComment, ExistingGithubIssue},
+    },
+    goal,
+    spanned::{Context as _, Result},
+};
+
+use crate::updates::{
+    help_wanted,
+    templates::{Feed, FeedEntry},
+    tldr_text,
+};
+
+/// Writes an Atom feed of the updates on all tracking issues in `milestone` into
+/// `directory/all.xml`, and one feed per goal into `directory/<goal>.xml`, where `<goal>`
+/// is the name of the goal document (or `issue-<number>` for issues without one).
+///
+/// The entries are the comments that show up in the monthly updates, newest first.
+pub fn generate_feeds(repository: &Repository, milestone: &str, directory: &Path) -> Result<()> {
+    let goal_documents = goal::goals_in_dir(&Path::new("src").join(milestone))?;
+    let goal_names: BTreeMap<u64, String> = goal_documents
+        .iter()
+        .filter_map(|document| {
+            let number = document.metadata.tracking_issue.as_ref()?.number;
+            let name = document.path.file_stem()?.to_str()?.to_string();
+            Some((number, name))
+        })
+        .collect();
+
+    let issues = list_issues_in_milestone(repository, milestone)?;
+    let tracking_issues: Vec<&ExistingGithubIssue> = issues
+        .iter()
+        .filter(|issue| issue.has_label("C-tracking-issue"))
+        .collect();
+
+    std::fs::create_dir_all(directory).with_path_context(directory, "creating feed directory")?;
+
+    let mut all_entries = vec![];
+    for &issue in &tracking_issues {
+        let issue_id = IssueId::new(repository.clone(), issue.number);
+        let entries = feed_entries(&issue_id, issue)?;
+
+        let name = match goal_names.get(&issue.number) {
+            Some(name) => name.clone(),
+            None => format!("issue-{}", issue.number),
+        };
+        let feed = build_feed(
+            issue_id.url(),
+            issue.title.clone(),
+            issue_id.url(),
+            entries.clone(),
+        );
+        write_feed(&directory.join(format!("{name}.xml")), feed)?;
+
+        all_entries.extend(entries);
+    }
+
+    // Timestamps like `2025-10-01T12:00:00Z` sort chronologically.
+    all_entries.sort_by(|a, b| b.published.cmp(&a.published));
+    let milestone_url = format!(
+        "https://github.com/{repository}/issues?q=label%3AC-tracking-issue+milestone%3A{milestone}"
+    );
+    let feed = build_feed(
+        milestone_url.clone(),
+        format!("Rust project goals {milestone}"),
+        milestone_url,
+        all_entries,
+    );
+    write_feed(&directory.join("all.xml"), feed)?;
+
+    eprintln!(
+        "wrote the feeds of {} tracking issue(s) in {milestone} into `{}`",
+        tracking_issues.len(),
+        directory.display()
+    );
+    Ok(())
+}
+
+/// The entries for the updates on `issue`, newest first.
+fn feed_entries(issue_id: &IssueId, issue: &ExistingGithubIssue) -> Result<Vec<FeedEntry>> {
+    let mut comments: Vec<&ExistingGithubComment> = issue
+        .comments
+        .iter()
+        .filter(|c| !c.should_hide_from_reports())
+        .collect();
+    comments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    comments
+        .into_iter()
+        .map(|comment| {
+            let tldr = tldr_text(comment).map(str::to_string);
+            let (has_help_wanted, help_wanted) =
+                help_wanted(issue_id, &tldr, std::slice::from_ref(comment))?;
+
+            let summary: Vec<String> = tldr
+                .into_iter()
+                .chain(
+                    help_wanted
+                        .into_iter()
+                        .map(|h| format!("Help wanted: {}", h.text.trim())),
+                )
+                .collect();
+
+            Ok(FeedEntry {
+                url: comment.url.clone(),
+                title: format!("{}: update from {}", issue.title, comment.author),
+                author: comment.author.trim_start_matches('@').to_string(),
+                published: comment.created_at.clone(),
+                summary: if summary.is_empty() {
+                    None
+                } else {
+                    Some(summary.join("\n\n"))
+                },
+                has_help_wanted,
+                content: comrak::markdown_to_html(&comment.body, &comrak::ComrakOptions::default()),
+            })
+        })
+        .collect()
+}
+
+/// A feed whose last update is that of its newest entry (or now, if it has none).
+fn build_feed(id: String, title: String, link: String, entries: Vec<FeedEntry>) -> Feed {
+    let updated = match entries.first() {
+        Some(entry) => entry.published.clone(),
+        None => chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    };
+    Feed {
+        id,
+        title,
+        link,
+        updated,
+        entries,
+    }
+}
+
+fn write_feed(path: &Path, feed: Feed) -> Result<()> {
+    std::fs::write(path, feed.render()?).with_path_context(path, "writing feed")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_project_goals_json::GithubIssueState;
+
+    #[test]
+    fn test_render_feed() {
+        let comment: ExistingGithubComment = serde_json::from_value(serde_json::json!({
+            "author": "@alice",
+            "body": "TL;DR: We landed the parser.\n\nMore details.",
+            "created_at": "2025-10-01T12:00:00Z",
+            "url": "https://github.com/rust-lang/rust-project-goals/issues/123#issuecomment-1",
+            "hidden": false,
+        }))
+        .unwrap();
+        let issue = ExistingGithubIssue {
+            number: 123,
+            title: "Faster builds".to_string(),
+            assignees: Default::default(),
+            comments: vec![comment],
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+            updated_at: None,
+        };
+        let issue_id = IssueId::new(Repository::new("rust-lang", "rust-project-goals"), 123);
+        let entries = feed_entries(&issue_id, &issue).unwrap();
+        let feed = build_feed(issue_id.url(), issue.title.clone(), issue_id.url(), entries)
+            .render()
+            .unwrap();
+
+        assert!(feed.contains("<title>Faster builds: update from @alice</title>"));
+        assert!(feed.contains("<name>@alice</name>"));
+        assert!(feed.contains("<uri>https://github.com/alice</uri>"));
+        assert!(feed.contains("<summary>We landed the parser.</summary>"));
+        assert!(feed.contains("<updated>2025-10-01T12:00:00Z</updated>"));
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use rust_project_goals::gh::{
+    backend::backend,
+    issue_id::Repository,
+    issues::{all_checkboxes, list_issues_in_milestone},
+    labels::GhLabel,
+};
+use rust_project_goals::spanned::Result;
+
+/// Record everything we read from github about the tracking issues in `milestone`
+/// into `directory`, so that the other commands can later run offline with `--github-fixtures`.
+/// The recording itself is done by the `Recorder` backend installed in `main`.
+pub fn record_fixtures(repository: &Repository, milestone: &str, directory: &Path) -> Result<()> {
+    backend().sanity_check()?;
+
+    GhLabel::list(repository)?;
+
+    let issues = list_issues_in_milestone(repository, milestone)?;
+
+    // Computing the progress fetches any issues and searches the bodies refer to,
+    // which records them as well.
+    all_checkboxes(&issues);
+
+    eprintln!(
+        "recorded {} issues from `{}` into `{}`",
+        issues.len(),
+        milestone,
+        directory.display()
+    );
+
+    Ok(())
+}
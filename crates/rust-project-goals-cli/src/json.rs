@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use rust_project_goals::gh::{
+    issue_id::Repository,
+    issues::{all_checkboxes, list_issues_in_milestone, ExistingGithubIssue},
+};
+use rust_project_goals::spanned::{Context as _, Result};
+use rust_project_goals_json::{
+    Progress, TrackingIssue, TrackingIssueUpdate, TrackingIssues, VERSION,
+};
+
+/// Export the tracking issues for `milestone` in the versioned JSON format
+/// defined by `rust-project-goals-json`.
+pub fn generate_json(
+    repository: &Repository,
+    milestone: &str,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    let issues = list_issues_in_milestone(repository, milestone)?;
+
+    let tracking_issues = TrackingIssues {
+        version: VERSION,
+        repository: repository.to_string(),
+        milestone: milestone.to_string(),
+        issues: issues
+            .iter()
+            .zip(all_checkboxes(&issues))
+            .map(|(issue, progress)| tracking_issue(issue, progress))
+            .collect(),
+    };
+
+    let output = serde_json::to_string_pretty(&tracking_issues)?;
+
+    if let Some(output_file) = output_file {
+        std::fs::write(output_file, output).with_path_context(output_file, "failed to write")?;
+    } else {
+        println!("{output}");
+    }
+
+    Ok(())
+}
+
+fn tracking_issue(issue: &ExistingGithubIssue, progress: Progress) -> TrackingIssue {
+    let mut comments = issue.comments.clone();
+    comments.sort_by_key(|c| c.created_at.clone());
+    comments.retain(|c| !c.should_hide_from_reports());
+
+    TrackingIssue {
+        number: issue.number,
+        title: issue.title.clone(),
+        flagship: issue.has_flagship_label(),
+        progress,
+        assignees: issue.assignees.iter().cloned().collect(),
+        updates: comments
+            .into_iter()
+            .map(|c| TrackingIssueUpdate {
+                author: c.author,
+                body: c.body,
+                created_at: c.created_at,
+                url: c.url,
+            })
+            .collect(),
+        state: issue.state,
+    }
+}
@@ -1,3 +1,3 @@
 pub mod updates;
 
-pub use updates::{render_updates, Order};
+pub use updates::{render_goal, render_updates, GoalView, Order, UpdatesFormat};
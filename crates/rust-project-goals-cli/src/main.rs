@@ -1,17 +1,30 @@
 use clap::Parser;
-use regex::Regex;
 use rust_project_goals::{
-    gh::issue_id::Repository,
+    config::GithubCacheConfig,
+    gh::{
+        backend::{self, Cache, Fixtures, GhCli, GithubApi, GithubBackend, Recorder},
+        issue_id::Repository,
+    },
     spanned::{Context as _, Result, Spanned},
+    team::{self, TeamDataOptions},
 };
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command as ProcessCommand, Stdio};
-use walkdir::WalkDir;
 
 mod cfp;
+mod check;
 mod csv_reports;
+mod feed;
+mod fixtures;
+mod json;
+mod migrate;
+mod request_updates;
 mod rfc;
+mod rollover;
+mod slate;
+mod snapshot;
+mod stale;
 mod team_repo;
 mod updates;
 
@@ -24,6 +37,44 @@ struct Opt {
     /// Repository to use if applicable
     #[arg(long, default_value = "rust-lang/rust-project-goals")]
     repository: Repository,
+
+    /// How to talk to github: through the `gh` CLI tool (the default)
+    /// or directly over HTTP with a token from `GITHUB_TOKEN` or `GH_TOKEN`.
+    #[arg(long, global = true, value_enum)]
+    github_backend: Option<GithubBackendKind>,
+
+    /// Read and write github data from this directory of recorded fixtures
+    /// instead of talking to github (see `record-fixtures`).
+    #[arg(long, global = true, conflicts_with = "github_backend")]
+    github_fixtures: Option<PathBuf>,
+
+    /// Read everything from github, rather than reusing what was cached by earlier runs.
+    #[arg(long, global = true)]
+    no_github_cache: bool,
+
+    /// Only read the Rust team data from the local snapshot, never downloading it
+    /// (see `team-data refresh`).
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Read the Rust team data from this local checkout of the rust-lang/team repository
+    /// (its `teams/*.toml` and `people/*.toml` files) instead of the team API.
+    #[arg(long, global = true)]
+    team_checkout: Option<PathBuf>,
+
+    /// Use the handlebars templates in this directory instead of the built-in ones with
+    /// the same name (e.g., `progress.hbs`, or `html/updates.hbs` for `--format html`).
+    /// Defaults to `directory` in the `[templates]` table of `rust-project-goals.toml`.
+    #[arg(long, global = true)]
+    templates: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, Debug)]
+enum GithubBackendKind {
+    /// Run the `gh` CLI tool.
+    Gh,
+    /// Use the github REST and GraphQL APIs directly.
+    Api,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -49,18 +100,66 @@ enum Command {
         dry_run: bool,
     },
 
-    /// Use `gh` CLI tool to create issues on the rust-lang/rust-project-goals repository
+    /// Copy the goals that continue from one milestone into the next one,
+    /// resetting their status and converting them to the 2026 "Team asks" format.
+    Rollover {
+        /// Milestone the goals continue from (e.g., `2025h2`).
+        from: String,
+
+        /// Milestone the goals continue into (e.g., `2026`).
+        to: String,
+
+        /// Goal to continue, named by its file (e.g., `cargo-plumbing`).
+        /// May be given multiple times. If not given, you are asked about each goal.
+        #[arg(long = "goal")]
+        goals: Vec<String>,
+
+        /// Continue all accepted goals without asking
+        #[arg(short = 'y', long = "yes", conflicts_with = "goals")]
+        yes: bool,
+
+        /// Overwrite goal documents that already exist in the new milestone
+        #[arg(short = 'f', long = "force")]
+        force: bool,
+
+        /// Dry run - don't make any changes, just show what would be done
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// Rewrite goal documents from the old "Ownership and team asks" format
+    /// into the 2026 "Team asks" format, in place.
+    Migrate {
+        /// Goal documents, or directories of goals (e.g., `src/2026`), to migrate
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Dry run - don't make any changes, just show what would be done
+        #[arg(short = 'n', long = "dry-run")]
+        dry_run: bool,
+    },
+
+    /// Create and update the tracking issues on the rust-lang/rust-project-goals repository
     Issues {
         path: PathBuf,
 
         /// Number of milliseconds to pause between github commands
-        /// to avoid rate limiting
-        #[arg(long, default_value = "500")]
-        sleep: u64,
+        /// to avoid rate limiting. Defaults to 500 with the `gh` backend
+        /// and 0 otherwise (the `api` backend backs off by itself).
+        #[arg(long)]
+        sleep: Option<u64>,
 
         /// Without this option, no action is taken.
         #[arg(long)]
         commit: bool,
+
+        /// Write the actions that would be taken to this file as JSON, instead of taking them.
+        #[arg(long, conflicts_with = "commit")]
+        plan_out: Option<PathBuf>,
+
+        /// Execute exactly the actions in a plan written by `--plan-out`.
+        #[arg(long, conflicts_with_all = ["commit", "plan_out"])]
+        apply_plan: Option<PathBuf>,
     },
 
     /// Generate the project-goal-owners team based on the owners found in `paths`.
@@ -74,8 +173,20 @@ enum Command {
         team_repo_path: PathBuf,
     },
 
-    /// Checks that the goal documents are well-formed, intended for use within CI
-    Check {},
+    /// Checks that the goal documents are well-formed, intended for use within CI.
+    /// Reports every problem found before failing.
+    Check {
+        /// How to report the problems
+        #[arg(long, value_enum, default_value_t)]
+        format: check::CheckFormat,
+    },
+
+    /// Checks that the progress of every tracking issue in a milestone can be computed,
+    /// reporting broken or cyclic `Tracked issues`, `Tracked PRs`, and `See also` references.
+    CheckProgress {
+        /// Milestone whose tracking issues we check (e.g., `2025h2`).
+        milestone: String,
+    },
 
     /// Generate markdown with the list of updates for each tracking issue.
     /// Collects goal updates.
@@ -108,6 +219,103 @@ enum Command {
         /// Filter to only include goals that have a champion from the specified team.
         #[arg(long)]
         with_champion_from: Option<String>,
+
+        /// Format of the output. Each format but `json` is rendered with its own
+        /// templates (see `templates/html` and `templates/email`).
+        #[arg(long, value_enum, default_value_t)]
+        format: updates::UpdatesFormat,
+    },
+
+    /// Generate the blog post introducing the slate of goals for a milestone,
+    /// from the summary of each goal (see `templates/introduce_slate.hbs`).
+    IntroduceSlate {
+        /// Milestone whose goals we introduce (e.g., `2025h2`).
+        milestone: String,
+
+        /// If specified, write the output into the given file (otherwise print to stdout).
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Ask the assignees of each open tracking issue in a milestone that has had no update
+    /// since a given date to post one (see `templates/request_updates.hbs`).
+    RequestUpdates {
+        /// Milestone whose tracking issues we ping (e.g., `2025h2`).
+        milestone: String,
+
+        /// Goals with an update on or after this date are left alone.
+        /// If not given, defaults to 1 week before the start of this month,
+        /// like the start date of `updates`.
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+
+        /// Without this option, the requests are only printed, not posted.
+        #[arg(long)]
+        commit: bool,
+    },
+
+    /// List the open tracking issues in a milestone that have had no update for a while,
+    /// grouped by point of contact and by champion team.
+    Stale {
+        /// Milestone whose tracking issues we check (e.g., `2025h2`).
+        milestone: String,
+
+        /// Number of days without an update after which a goal is stale.
+        /// Defaults to `after_days` in the `[stale]` table of `rust-project-goals.toml`.
+        #[arg(long)]
+        days: Option<u64>,
+
+        /// Also prepare a reminder comment for each stale goal (see `templates/stale_reminder.hbs`).
+        #[arg(long)]
+        remind: bool,
+
+        /// Without this option, the reminders are only printed, not posted.
+        #[arg(long, requires = "remind")]
+        commit: bool,
+    },
+
+    /// Export the tracking issues for a milestone as JSON, in the format
+    /// defined by the `rust-project-goals-json` crate.
+    Json {
+        /// Milestone for which we generate tracking issue data (e.g., `2024h2`).
+        milestone: String,
+
+        /// If specified, write the output into the given file (otherwise print to stdout).
+        #[arg(long)]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Write Atom feeds of the updates posted on the tracking issues in a milestone:
+    /// one for the whole milestone (`all.xml`) and one per goal (`<goal>.xml`).
+    Feeds {
+        /// Milestone whose tracking issues we read (e.g., `2025h2`).
+        milestone: String,
+
+        /// Directory to write the feeds into. Defaults to `book/html/<milestone>/feeds`,
+        /// so that they are published with the book when run after `mdbook build`.
+        #[arg(long)]
+        directory: Option<PathBuf>,
+    },
+
+    /// Record today's progress of every tracking issue in a milestone into its progress
+    /// history, from which the book draws burn-up charts (see `(((BURN-UP CHART)))`).
+    SnapshotProgress {
+        /// Milestone whose tracking issues we record (e.g., `2025h2`).
+        milestone: String,
+
+        /// Milestone directory holding the progress history. Defaults to `src/<milestone>`.
+        #[arg(long)]
+        directory: Option<PathBuf>,
+    },
+
+    /// Record the github data for a milestone into a fixtures directory,
+    /// so that it can later be used with `--github-fixtures`.
+    RecordFixtures {
+        /// Milestone whose tracking issues we record (e.g., `2024h2`).
+        milestone: String,
+
+        /// Directory in which to store the fixtures.
+        directory: PathBuf,
     },
 
     /// Generate various CSV reports
@@ -115,6 +323,32 @@ enum Command {
         #[command(subcommand)]
         cmd: CSVReports,
     },
+
+    /// Manage the cache of github data kept between runs
+    Cache {
+        #[command(subcommand)]
+        cmd: CacheCommand,
+    },
+
+    /// Manage the local snapshot of the Rust team data
+    TeamData {
+        #[command(subcommand)]
+        cmd: TeamDataCommand,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum CacheCommand {
+    /// Remove everything from the cache.
+    Clear,
+    /// Show what is in the cache.
+    Stats,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum TeamDataCommand {
+    /// Download the team data and replace the snapshot, however old it is.
+    Refresh,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -129,6 +363,12 @@ enum CSVReports {
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
 
+    init_github_backend(&opt)?;
+    init_team_data(&opt)?;
+    if let Some(templates) = &opt.templates {
+        updates::templates::set_templates_dir(templates.clone())?;
+    }
+
     match &opt.cmd {
         Command::FCP { path } => {
             rfc::generate_comment(&path)?;
@@ -142,8 +382,27 @@ fn main() -> Result<()> {
             cfp::create_cfp(timeframe, *force, *dry_run)?;
         }
 
-        Command::Check {} => {
-            check()?;
+        Command::Rollover {
+            from,
+            to,
+            goals,
+            yes,
+            force,
+            dry_run,
+        } => {
+            rollover::rollover(from, to, goals, *yes, *force, *dry_run)?;
+        }
+
+        Command::Migrate { paths, dry_run } => {
+            migrate::migrate(paths, *dry_run)?;
+        }
+
+        Command::Check { format } => {
+            check::check(*format)?;
+        }
+
+        Command::CheckProgress { milestone } => {
+            check::check_progress(&opt.repository, milestone)?;
         }
 
         Command::RFC { path } => {
@@ -154,8 +413,18 @@ fn main() -> Result<()> {
             path,
             commit,
             sleep,
+            plan_out,
+            apply_plan,
         } => {
-            rfc::generate_issues(&opt.repository, path, *commit, *sleep).map_err(|e| {
+            rfc::generate_issues(
+                &opt.repository,
+                path,
+                *commit,
+                *sleep,
+                plan_out.as_deref(),
+                apply_plan.as_deref(),
+            )
+            .map_err(|e| {
                 e.wrap_str(Spanned::here(
                     "failed to adjust issues; rerun command to resume",
                 ))
@@ -176,6 +445,7 @@ fn main() -> Result<()> {
             start_date,
             end_date,
             with_champion_from,
+            format,
         } => generate_updates(
             &opt.repository,
             milestone,
@@ -184,37 +454,108 @@ fn main() -> Result<()> {
             end_date,
             *vscode,
             with_champion_from.as_deref(),
+            *format,
         )?,
 
+        Command::IntroduceSlate {
+            milestone,
+            output_file,
+        } => slate::introduce_slate(&opt.repository, milestone, output_file.as_deref())?,
+
+        Command::RequestUpdates {
+            milestone,
+            since,
+            commit,
+        } => request_updates::request_updates(&opt.repository, milestone, *since, *commit)?,
+
+        Command::Stale {
+            milestone,
+            days,
+            remind,
+            commit,
+        } => stale::stale(&opt.repository, milestone, *days, *remind, *commit)?,
+
+        Command::Json {
+            milestone,
+            output_file,
+        } => json::generate_json(&opt.repository, milestone, output_file.as_deref())?,
+
+        Command::Feeds {
+            milestone,
+            directory,
+        } => {
+            let directory = match directory {
+                Some(directory) => directory.clone(),
+                None => Path::new("book/html").join(milestone).join("feeds"),
+            };
+            feed::generate_feeds(&opt.repository, milestone, &directory)?
+        }
+
+        Command::SnapshotProgress {
+            milestone,
+            directory,
+        } => {
+            let directory = match directory {
+                Some(directory) => directory.clone(),
+                None => Path::new("src").join(milestone),
+            };
+            snapshot::snapshot_progress(&opt.repository, milestone, &directory)?
+        }
+
+        Command::RecordFixtures {
+            milestone,
+            directory,
+        } => fixtures::record_fixtures(&opt.repository, milestone, directory)?,
+
         Command::CSV { cmd } => csv_reports::csv(&opt.repository, cmd)?,
+
+        Command::Cache { cmd } => cache(cmd)?,
+
+        Command::TeamData {
+            cmd: TeamDataCommand::Refresh,
+        } => {
+            for path in team::refresh_snapshot()? {
+                println!("Wrote {}", path.display());
+            }
+        }
     }
 
     Ok(())
 }
 
-fn check() -> Result<()> {
-    // Look for all directories like `2024h2` or `2025h1` and load goals from those directories.
-    let regex = Regex::new(r"\d\d\d\dh[12]")?;
+/// The github backend selected on the command line, if any.
+fn selected_github_backend(opt: &Opt) -> Option<Box<dyn GithubBackend>> {
+    if let Some(directory) = &opt.github_fixtures {
+        return Some(Box::new(Fixtures::new(directory)));
+    }
 
-    for entry in WalkDir::new("src") {
-        let entry = entry?;
+    let backend: Box<dyn GithubBackend> = match opt.github_backend {
+        Some(GithubBackendKind::Gh) => Box::new(GhCli),
+        Some(GithubBackendKind::Api) => Box::new(GithubApi::new()),
+        None if opt.no_github_cache => return Some(backend::uncached_default_backend()),
+        None => return None,
+    };
 
-        if !entry.file_type().is_dir() {
-            continue;
-        }
-
-        let Some(name) = entry.file_name().to_str() else {
-            continue;
-        };
+    if opt.no_github_cache {
+        Some(backend)
+    } else {
+        Some(Box::new(Cache::from_config(backend)))
+    }
+}
 
-        if !regex.is_match(name) {
-            continue;
-        }
+fn init_github_backend(opt: &Opt) -> Result<()> {
+    let selected = selected_github_backend(opt);
 
-        let _goals = rust_project_goals::goal::goals_in_dir(entry.path())?;
+    if let Command::RecordFixtures { directory, .. } = &opt.cmd {
+        // Record whatever we read through the selected backend.
+        let inner = selected.unwrap_or_else(backend::default_backend);
+        return backend::set_backend(Box::new(Recorder::new(inner, Fixtures::new(directory))));
     }
 
-    Ok(())
+    match selected {
+        Some(selected) => backend::set_backend(selected),
+        None => Ok(()),
+    }
 }
 
 fn generate_updates(
@@ -225,6 +566,7 @@ fn generate_updates(
     end_date: &Option<chrono::NaiveDate>,
     vscode: bool,
     with_champion_from: Option<&str>,
+    format: updates::UpdatesFormat,
 ) -> Result<()> {
     if output_file.is_none() && !vscode {
         rust_project_goals::spanned::bail_here!(
@@ -245,6 +587,7 @@ fn generate_updates(
         with_champion_from,
         true,
         updates::Order::default(),
+        format,
     )?;
 
     // Make sure the `<pre>` code blocks have at least one empty line
@@ -254,7 +597,10 @@ fn generate_updates(
     //
     // See the analogous change in
     // `MarkdownProcessor::process_markdown`.
-    let output = output.replace("<pre>", "\n\n<pre>");
+    let output = match format {
+        updates::UpdatesFormat::Blog => output.replace("<pre>", "\n\n<pre>"),
+        _ => output,
+    };
 
     if let Some(output_file) = output_file {
         std::fs::write(&output_file, output).with_path_context(output_file, "failed to write")?;
@@ -280,3 +626,39 @@ fn generate_updates(
 
     Ok(())
 }
+
+fn init_team_data(opt: &Opt) -> Result<()> {
+    if !opt.offline && opt.team_checkout.is_none() {
+        return Ok(());
+    }
+
+    let defaults = TeamDataOptions::from_environment()?;
+    team::set_options(TeamDataOptions {
+        offline: opt.offline || defaults.offline,
+        team_checkout: opt.team_checkout.clone().or(defaults.team_checkout),
+        ..defaults
+    })
+}
+
+fn cache(cmd: &CacheCommand) -> Result<()> {
+    let config = GithubCacheConfig::from_working_directory()?;
+    match cmd {
+        CacheCommand::Clear => {
+            backend::clear_cache(&config.directory)?;
+            println!("Cleared {}", config.directory.display());
+        }
+        CacheCommand::Stats => {
+            let stats = backend::cache_stats(&config.directory, config.ttl())?;
+            println!("Cache directory: {}", config.directory.display());
+            println!("Issues:          {}", stats.issues);
+            println!("Milestones:      {}", stats.milestones);
+            println!("Searches:        {}", stats.searches);
+            println!(
+                "Stale:           {} (older than {} minutes)",
+                stats.stale, config.ttl_minutes
+            );
+            println!("Size:            {} KiB", stats.bytes.div_ceil(1024));
+        }
+    }
+    Ok(())
+}
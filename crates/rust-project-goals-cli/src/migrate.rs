@@ -0,0 +1,559 @@
+//! Converting goal documents from the pre-2026 format ("Ownership and team asks",
+//! with a list of tasks and the specific things asked of each team) into the 2026
+//! format ("Team asks", with a support level for each team).
+//!
+//! The conversion is expressed as a set of edits to the text of the document,
+//! so that everything outside of the sections we convert is left untouched.
+
+use std::{cmp::Reverse, ops::Range, path::PathBuf};
+
+use rust_project_goals::{
+    config::Configuration,
+    goal::{self, GoalDocument, SupportLevel},
+    markwaydown::{self, Section},
+    spanned::{Context as _, Result, Spanned},
+    util::format_table,
+};
+
+use crate::rfc::plan::line_diff;
+
+/// Rewrites the goal documents in `paths` (files, or directories of goals) that use the
+/// old format into the 2026 format, in place. With `dry_run`, prints the changes instead.
+pub fn migrate(paths: &[PathBuf], dry_run: bool) -> Result<()> {
+    if dry_run {
+        println!("Dry run mode - no changes will be made");
+    }
+
+    let mut goal_documents = vec![];
+    for path in paths {
+        if path.is_dir() {
+            goal_documents.extend(goal::goals_in_dir(path)?);
+        } else if let Some(goal_document) = goal::goal_in_file(path)? {
+            goal_documents.push(goal_document);
+        } else {
+            println!("Skipping {}: not a goal document", path.display());
+        }
+    }
+
+    let mut migrated = 0;
+    for goal_document in &goal_documents {
+        let path = &goal_document.path;
+        let text = std::fs::read_to_string(path).with_path_context(path, "Failed to read goal")?;
+
+        let edits = team_asks_edits(goal_document, &text)?;
+        if edits.is_empty() {
+            continue;
+        }
+        let new_text = apply_edits(&text, edits);
+
+        if dry_run {
+            println!("Would migrate {}:", path.display());
+            print_changes(&text, &new_text);
+        } else {
+            std::fs::write(path, new_text).with_path_context(path, "Failed to write to file")?;
+            println!("Migrated {}", path.display());
+        }
+        migrated += 1;
+    }
+
+    println!("\n{migrated} goal(s) migrated to the `Team asks` format.");
+    if migrated > 0 {
+        println!("Review the suggested support levels with the point of contact of each goal.");
+    }
+
+    Ok(())
+}
+
+fn print_changes(before: &str, after: &str) {
+    for line in line_diff(before, after).lines() {
+        if !line.starts_with(' ') {
+            println!("{line}");
+        }
+    }
+    println!();
+}
+
+/// Replace the bytes in `range` of a goal document with `text`.
+#[derive(Debug)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+/// Apply `edits` to `text`. The edits must not overlap, except that an insertion
+/// (an empty range) may start where a replacement starts, in which case the
+/// inserted text goes before the replacement.
+pub fn apply_edits(text: &str, mut edits: Vec<Edit>) -> String {
+    // Apply edits back to front so that the earlier ranges remain valid.
+    edits.sort_by_key(|edit| (Reverse(edit.range.start), Reverse(edit.range.end)));
+
+    let mut text = text.to_string();
+    for edit in edits {
+        text.replace_range(edit.range, &edit.text);
+    }
+    text
+}
+
+/// The edits that convert the "Ownership and team asks" section of `document` (whose
+/// current contents are `text`) into a "Team asks" section. Tasks that are not team asks
+/// move into a "Work items over the next year" table at the end of the motivation.
+/// Text in the section other than the task tables is kept.
+///
+/// Returns no edits if the document already uses the new format.
+pub fn team_asks_edits(document: &GoalDocument, text: &str) -> Result<Vec<Edit>> {
+    let Some(asks) = document.team_involvement.as_asks() else {
+        return Ok(vec![]);
+    };
+
+    let sections = markwaydown::parse(&document.path)?;
+    let Some(ownership_index) = sections
+        .iter()
+        .position(|section| section.title == "Ownership and team asks")
+    else {
+        return Ok(vec![]);
+    };
+    let level = sections[ownership_index].level;
+
+    // Group the team asks by subgoal, and within each subgoal by team,
+    // keeping the order in which they appear in the document.
+    let mut groups: Vec<(Option<&str>, Vec<TeamRow>)> = vec![];
+    for ask in asks {
+        let subgoal = ask.goal_titles.get(1).map(|title| title.as_str());
+        let group = match groups.iter().position(|(s, _)| *s == subgoal) {
+            Some(index) => &mut groups[index].1,
+            None => {
+                groups.push((subgoal, vec![]));
+                &mut groups.last_mut().unwrap().1
+            }
+        };
+
+        let support_level = suggested_support_level(&ask.ask_description);
+        let note = if ask.notes.trim().is_empty() {
+            ask.ask_description.clone()
+        } else {
+            format!("{} ({})", ask.ask_description, ask.notes.trim())
+        };
+
+        for team in &ask.teams {
+            let team = team.to_string();
+            match group.iter_mut().find(|row| row.team == team) {
+                Some(row) => {
+                    row.support_level = row.support_level.max(support_level);
+                    row.notes.push(note.clone());
+                }
+                None => group.push(TeamRow {
+                    team,
+                    support_level,
+                    notes: vec![note.clone()],
+                }),
+            }
+        }
+    }
+
+    // Rename the section and replace the task table of the section and of each subgoal
+    // with a team asks table. Everything else in the section (such as who champions the
+    // goal, or the introduction of a subgoal) stays where it is.
+    let ownership_heading = &sections[ownership_index];
+    let mut edits = vec![Edit {
+        range: heading_start(text, ownership_heading)
+            ..line_end(text, ownership_heading.title.span.bytes.start),
+        text: format!("{} Team asks", "#".repeat(level)),
+    }];
+    for index in ownership_index..section_range_end(&sections, ownership_index) {
+        let section = &sections[index];
+        let Some(table) = section.tables.first() else {
+            continue;
+        };
+        let subgoal = if index == ownership_index {
+            None
+        } else {
+            Some(section.title.as_str())
+        };
+        let table_range =
+            line_start(text, table.span.bytes.start)..next_line_start(text, table.span.bytes.end);
+
+        match groups.iter().find(|(s, _)| *s == subgoal) {
+            Some((_, rows)) => edits.push(Edit {
+                range: table_range,
+                text: team_asks_table(rows),
+            }),
+            // A subgoal with nothing but work items has no team asks left.
+            None if subgoal.is_some() && section.text.trim().is_empty() => edits.push(Edit {
+                range: heading_start(text, section)..next_heading_start(text, &sections, index),
+                text: String::new(),
+            }),
+            None => edits.push(Edit {
+                range: table_range.start..skip_blank_line(text, table_range.end),
+                text: String::new(),
+            }),
+        }
+    }
+
+    // Tasks done by the owners (or help wanted) become work items, which the 2026 template
+    // puts at the end of the motivation.
+    let motivation_index = sections
+        .iter()
+        .position(|section| section.title == "Motivation");
+    let work_items_level = match motivation_index {
+        Some(index) => sections[index].level + 1,
+        None => level,
+    };
+    let mut work_items = String::new();
+    for goal_plan in &document.goal_plans {
+        let rows: Vec<Vec<Spanned<String>>> = goal_plan
+            .plan_items
+            .iter()
+            .filter(|plan_item| !plan_item.is_team_ask())
+            .map(|plan_item| {
+                vec![
+                    plan_item.text.clone(),
+                    Spanned::here(plan_item.owners.clone()),
+                    Spanned::here(plan_item.notes.clone()),
+                ]
+            })
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+
+        if let Some(subgoal) = &goal_plan.subgoal {
+            work_items.push_str(&format!(
+                "{} {}\n\n",
+                "#".repeat(work_items_level + 1),
+                subgoal.as_str()
+            ));
+        }
+        work_items.push_str(&table(&["Task", "Owner(s)", "Notes"], rows));
+        work_items.push('\n');
+    }
+
+    if !work_items.is_empty() {
+        work_items.insert_str(
+            0,
+            &format!(
+                "{} Work items over the next year\n\n",
+                "#".repeat(work_items_level)
+            ),
+        );
+        let position = match motivation_index {
+            Some(index) => section_range(text, &sections, index).end,
+            None => heading_start(text, ownership_heading),
+        };
+        edits.push(Edit {
+            range: position..position,
+            text: work_items,
+        });
+    }
+
+    Ok(edits)
+}
+
+/// The support level we suggest for a team that was asked for `ask` in the old format,
+/// as configured in `rust-project-goals.toml`. This is only a starting point;
+/// the result should be reviewed with the goal owners.
+fn suggested_support_level(ask: &str) -> SupportLevel {
+    Configuration::get()
+        .team_asks
+        .get(ask)
+        .and_then(|details| details.support_level)
+        .unwrap_or(SupportLevel::Small)
+}
+
+struct TeamRow {
+    /// The team, formatted as a link (e.g., `[lang]`)
+    team: String,
+    support_level: SupportLevel,
+    notes: Vec<String>,
+}
+
+fn team_asks_table(rows: &[TeamRow]) -> String {
+    table(
+        &["Team", "Support level", "Notes"],
+        rows.iter()
+            .map(|row| {
+                vec![
+                    Spanned::here(row.team.clone()),
+                    Spanned::here(row.support_level.to_string()),
+                    Spanned::here(row.notes.join("; ")),
+                ]
+            })
+            .collect(),
+    )
+}
+
+fn table(header: &[&str], rows: Vec<Vec<Spanned<String>>>) -> String {
+    let mut table = vec![header
+        .iter()
+        .map(|column| Spanned::here(column.to_string()))
+        .collect()];
+    table.extend(rows);
+    format_table(&table)
+}
+
+/// The bytes of the section `sections[index]`, from the start of its heading line
+/// up to the next heading at the same or a higher level (so including any subsections).
+fn section_range(text: &str, sections: &[Section], index: usize) -> Range<usize> {
+    let start = heading_start(text, &sections[index]);
+    let end = match sections.get(section_range_end(sections, index)) {
+        Some(section) => heading_start(text, section),
+        None => text.len(),
+    };
+    start..end
+}
+
+/// The index of the first section after `sections[index]` that is not one of its subsections.
+fn section_range_end(sections: &[Section], index: usize) -> usize {
+    let level = sections[index].level;
+    sections[index + 1..]
+        .iter()
+        .position(|section| section.level <= level)
+        .map(|position| index + 1 + position)
+        .unwrap_or(sections.len())
+}
+
+/// The start of the heading line of the section after `sections[index]` (of any level),
+/// or the end of the text if it is the last section.
+fn next_heading_start(text: &str, sections: &[Section], index: usize) -> usize {
+    match sections.get(index + 1) {
+        Some(section) => heading_start(text, section),
+        None => text.len(),
+    }
+}
+
+fn heading_start(text: &str, section: &Section) -> usize {
+    line_start(text, section.title.span.bytes.start)
+}
+
+/// The start of the line containing the byte `offset`.
+fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .rfind('\n')
+        .map(|newline| newline + 1)
+        .unwrap_or(0)
+}
+
+/// The end of the line containing the byte `offset` (not including the newline).
+fn line_end(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .find('\n')
+        .map(|newline| offset + newline)
+        .unwrap_or(text.len())
+}
+
+/// The start of the line after the one containing the byte `offset`.
+fn next_line_start(text: &str, offset: usize) -> usize {
+    match line_end(text, offset) {
+        end if end < text.len() => end + 1,
+        end => end,
+    }
+}
+
+/// Skips a blank line starting at `offset`, if there is one.
+fn skip_blank_line(text: &str, offset: usize) -> usize {
+    if text[offset..].starts_with('\n') {
+        offset + 1
+    } else {
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_project_goals::team;
+
+    #[test]
+    fn test_team_asks_edits() {
+        team::test_support::use_test_checkout();
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("faster-builds.md");
+        let text = r#"# Faster builds
+
+| Metadata         |          |
+|:-----------------|----------|
+| Point of contact | @alice   |
+| Status           | Proposed |
+
+## Summary
+
+Make builds faster.
+
+## Motivation
+
+Builds are slow.
+
+## Ownership and team asks
+
+| Task                         | Owner(s) or team(s)    | Notes     |
+|------------------------------|------------------------|-----------|
+| Implementation               | @alice                 |           |
+| Discussion and moral support | ![Team][] [compiler][] |           |
+| Standard reviews             | ![Team][] [compiler][] | Small PRs |
+| RFC decision                 | ![Team][] [lang][]     |           |
+
+## Frequently asked questions
+"#;
+        std::fs::write(&path, text).unwrap();
+
+        let document = goal::goal_in_file(&path).unwrap().unwrap();
+        let edits = team_asks_edits(&document, text).unwrap();
+        assert_eq!(
+            apply_edits(text, edits),
+            r#"# Faster builds
+
+| Metadata         |          |
+|:-----------------|----------|
+| Point of contact | @alice   |
+| Status           | Proposed |
+
+## Summary
+
+Make builds faster.
+
+## Motivation
+
+Builds are slow.
+
+### Work items over the next year
+
+| Task           | Owner(s) | Notes |
+| :--            | :--    | :-- |
+| Implementation | @alice |  |
+
+## Team asks
+
+| Team       | Support level | Notes                                                      |
+| :--        | :--    | :--                                                        |
+| [compiler] | Small  | Discussion and moral support; Standard reviews (Small PRs) |
+| [lang]     | Medium | RFC decision                                               |
+
+## Frequently asked questions
+"#
+        );
+    }
+
+    #[test]
+    fn test_team_asks_edits_keep_prose() {
+        team::test_support::use_test_checkout();
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("stabilize-flags.md");
+        let text = r#"# Stabilize flags
+
+| Metadata         |          |
+|:-----------------|----------|
+| Point of contact | @alice   |
+| Status           | Proposed |
+
+## Summary
+
+Stabilize the flags.
+
+## Motivation
+
+The flags are unstable.
+
+## Ownership and team asks
+
+**Owner:** @alice will champion this project goal.
+
+| Task                         | Owner(s) or team(s)    | Notes |
+|------------------------------|------------------------|-------|
+| Implementation               | @alice                 |       |
+| Discussion and moral support | ![Team][] [compiler][] |       |
+
+Which flags get stabilized depends on bandwidth.
+
+### Stabilize the flag
+
+| Task                        | Owner(s) or team(s) | Notes |
+|-----------------------------|---------------------|-------|
+| Author stabilization report | @alice              |       |
+| Stabilization decision      | ![Team][] [lang][]  |       |
+
+### Write the docs
+
+| Task           | Owner(s) or team(s) | Notes |
+|----------------|---------------------|-------|
+| Write the docs | @alice              |       |
+
+## Frequently asked questions
+"#;
+        std::fs::write(&path, text).unwrap();
+
+        let document = goal::goal_in_file(&path).unwrap().unwrap();
+        let edits = team_asks_edits(&document, text).unwrap();
+        assert_eq!(
+            apply_edits(text, edits),
+            r#"# Stabilize flags
+
+| Metadata         |          |
+|:-----------------|----------|
+| Point of contact | @alice   |
+| Status           | Proposed |
+
+## Summary
+
+Stabilize the flags.
+
+## Motivation
+
+The flags are unstable.
+
+### Work items over the next year
+
+| Task           | Owner(s) | Notes |
+| :--            | :--    | :-- |
+| Implementation | @alice |  |
+
+#### Stabilize the flag
+
+| Task                        | Owner(s) | Notes |
+| :--                         | :--    | :-- |
+| Author stabilization report | @alice |  |
+
+#### Write the docs
+
+| Task           | Owner(s) | Notes |
+| :--            | :--    | :-- |
+| Write the docs | @alice |  |
+
+## Team asks
+
+**Owner:** @alice will champion this project goal.
+
+| Team       | Support level | Notes                        |
+| :--        | :--   | :--                          |
+| [compiler] | Vibes | Discussion and moral support |
+
+Which flags get stabilized depends on bandwidth.
+
+### Stabilize the flag
+
+| Team   | Support level | Notes                  |
+| :--    | :--    | :--                    |
+| [lang] | Medium | Stabilization decision |
+
+## Frequently asked questions
+"#
+        );
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let edits = vec![
+            Edit {
+                range: 0..1,
+                text: "A".to_string(),
+            },
+            Edit {
+                range: 2..4,
+                text: "replaced".to_string(),
+            },
+            Edit {
+                range: 2..2,
+                text: "inserted ".to_string(),
+            },
+        ];
+        assert_eq!(apply_edits("a-bc-d", edits), "A-inserted replaced-d");
+    }
+}
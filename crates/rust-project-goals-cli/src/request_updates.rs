@@ -0,0 +1,178 @@
+//! Asking the owners of goals for a status update before the monthly blog post is generated.
+
+use chrono::NaiveDate;
+use rust_project_goals::{
+    gh::{
+        backend::backend,
+        issue_id::Repository,
+        issues::{
+            create_comment, list_issues_in_milestone, ExistingGithubIssue, UPDATE_REQUEST_MARKER,
+        },
+    },
+    spanned::Result,
+};
+use rust_project_goals_json::GithubIssueState;
+
+use crate::updates::{default_start_date, templates::UpdateRequest};
+
+/// Posts a request for an update, mentioning the assignees, on each open tracking issue in
+/// `milestone` without a comment on or after `since` (by default, the start date used by
+/// `cargo rpg updates`). The requests are only printed unless `commit` is set.
+///
+/// The request itself is recorded on the issue, so an issue that was already asked since
+/// `since` is skipped and running the command again does not ping anyone twice.
+pub fn request_updates(
+    repository: &Repository,
+    milestone: &str,
+    since: Option<NaiveDate>,
+    commit: bool,
+) -> Result<()> {
+    let since = since.unwrap_or_else(default_start_date);
+    let issues = list_issues_in_milestone(repository, milestone)?;
+
+    if !commit {
+        println!("Dry run mode - pass `--commit` to post the requests");
+    }
+
+    let mut requested = 0;
+    for issue in issues {
+        if !issue.has_label("C-tracking-issue") || issue.state != GithubIssueState::Open {
+            continue;
+        }
+
+        let number = issue.number;
+        if has_update_since(&issue, since) {
+            continue;
+        }
+        if was_requested_since(&issue, since) {
+            println!("Skipping #{number}: already asked for an update since {since}");
+            continue;
+        }
+        if issue.assignees.is_empty() {
+            println!("Skipping #{number}: no assignees to ask for an update");
+            continue;
+        }
+
+        let text = UpdateRequest {
+            title: issue.title.clone(),
+            mentions: issue
+                .assignees
+                .iter()
+                .map(|assignee| format!("@{assignee}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            since: since.to_string(),
+        }
+        .render()?;
+        let body = format!("{UPDATE_REQUEST_MARKER}\n{text}");
+
+        if commit {
+            create_comment(repository, number, &body)?;
+            println!("Asked for an update on #{number}");
+            std::thread::sleep(backend().pause_between_writes());
+        } else {
+            println!("Would post on #{number}:\n\n{body}\n");
+        }
+        requested += 1;
+    }
+
+    if commit {
+        println!("Asked for {requested} update(s) in {milestone}");
+    } else {
+        println!("Would ask for {requested} update(s) in {milestone}");
+    }
+    Ok(())
+}
+
+/// True if someone posted an update on `issue` on or after `since`.
+fn has_update_since(issue: &ExistingGithubIssue, since: NaiveDate) -> bool {
+    issue
+        .comments
+        .iter()
+        .any(|c| !c.should_hide_from_reports() && c.created_at_date() >= since)
+}
+
+/// True if we already asked for an update on `issue` on or after `since`.
+fn was_requested_since(issue: &ExistingGithubIssue, since: NaiveDate) -> bool {
+    issue
+        .comments
+        .iter()
+        .any(|c| c.body.trim().starts_with(UPDATE_REQUEST_MARKER) && c.created_at_date() >= since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_project_goals::gh::issues::{ExistingGithubComment, LOCK_TEXT};
+
+    fn comment(body: &str, created_at: &str) -> ExistingGithubComment {
+        serde_json::from_value(serde_json::json!({
+            "author": "@alice",
+            "body": body,
+            "created_at": created_at,
+            "url": "https://github.com/rust-lang/rust-project-goals/issues/1#issuecomment-1",
+            "hidden": false,
+        }))
+        .unwrap()
+    }
+
+    fn issue(comments: Vec<ExistingGithubComment>) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number: 1,
+            title: "Faster builds".to_string(),
+            assignees: Default::default(),
+            comments,
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+            updated_at: None,
+        }
+    }
+
+    fn request(created_at: &str) -> ExistingGithubComment {
+        comment(
+            &format!("{UPDATE_REQUEST_MARKER}\nPlease post an update."),
+            created_at,
+        )
+    }
+
+    #[test]
+    fn test_was_requested_since() {
+        let since = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+
+        let before = issue(vec![request("2025-09-30T23:59:59Z")]);
+        assert!(!was_requested_since(&before, since));
+
+        let after = issue(vec![
+            request("2025-09-15T12:00:00Z"),
+            request("2025-10-01T00:00:00Z"),
+        ]);
+        assert!(was_requested_since(&after, since));
+    }
+
+    #[test]
+    fn test_has_update_since() {
+        let since = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+
+        let updated = issue(vec![comment(
+            "We landed the parser.",
+            "2025-10-02T12:00:00Z",
+        )]);
+        assert!(has_update_since(&updated, since));
+
+        let updated_before = issue(vec![comment(
+            "We landed the parser.",
+            "2025-09-30T12:00:00Z",
+        )]);
+        assert!(!has_update_since(&updated_before, since));
+
+        // Our own requests (and other automated comments) are not updates.
+        let automated = issue(vec![
+            comment(LOCK_TEXT, "2025-10-02T12:00:00Z"),
+            request("2025-10-03T12:00:00Z"),
+        ]);
+        assert!(!has_update_since(&automated, since));
+        assert!(was_requested_since(&automated, since));
+    }
+}
@@ -7,14 +7,17 @@ use std::{
 };
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use rust_project_goals::{
     gh::{
+        backend::backend,
         issue_id::{IssueId, Repository},
         issues::{
-            change_milestone, change_title, create_comment, create_issue, fetch_issue,
-            list_issues_in_milestone, lock_issue, sync_assignees, sync_labels, update_issue_body,
-            CONTINUING_GOAL_PREFIX, FLAGSHIP_LABEL, LOCK_TEXT,
+            change_milestone, change_title, checkboxes, close_issue, create_comment, create_issue,
+            fetch_current_issue, fetch_issue, list_issues_in_milestone, lock_issue, sync_assignees,
+            sync_labels, update_issue_body, ExistingGithubIssue, IssueStateReason,
+            CLOSING_GOAL_PREFIX, CONTINUING_GOAL_PREFIX, FLAGSHIP_LABEL, LOCK_TEXT,
         },
         labels::GhLabel,
     },
@@ -22,6 +25,13 @@ use rust_project_goals::{
     spanned::{self, Context, Error, Result, Spanned},
     team::{get_person_data, TeamName},
 };
+use rust_project_goals_json::{GithubIssueState, Progress};
+
+mod body;
+pub(crate) mod plan;
+
+use body::{adopt_body, body_hash, generated_region, sync_body, BodySync};
+use plan::{line_diff, IssuesPlan};
 
 fn validate_path(path: &Path) -> Result<String> {
     if !path.is_dir() {
@@ -100,14 +110,19 @@ pub fn generate_issues(
     repository: &Repository,
     path: &Path,
     commit: bool,
-    sleep: u64,
+    sleep: Option<u64>,
+    plan_out: Option<&Path>,
+    apply_plan: Option<&Path>,
 ) -> Result<()> {
-    // Verify the `gh` client is installed to compute which actions need to be taken in the repo.
-    let sanity_check = Command::new("gh").arg("--version").output();
-    if sanity_check.is_err() {
-        spanned::bail_here!(
-            "The github `gh` client is missing and needs to be installed and configured with a token."
-        );
+    // Verify we can talk to github to compute which actions need to be taken in the repo.
+    backend().sanity_check()?;
+    let sleep = match sleep {
+        Some(ms) => Duration::from_millis(ms),
+        None => backend().pause_between_writes(),
+    };
+
+    if let Some(apply_plan) = apply_plan {
+        return execute_plan(repository, path, apply_plan, sleep);
     }
 
     // Hacky but works: we loop because after creating the issue, we sometimes have additional sync to do,
@@ -126,56 +141,52 @@ pub fn generate_issues(
         }
         let timeframe = validate_path(path)?;
 
-        let mut goal_documents = goal::goals_in_dir(path)?;
-        goal_documents.retain(|gd| gd.is_not_not_accepted());
+        let (goal_documents, rejected_goal_documents): (Vec<_>, Vec<_>) = goal::goals_in_dir(path)?
+            .into_iter()
+            .partition(|gd| gd.is_not_not_accepted());
+
+        // the list of existing issues in the target milestone
+        let milestone_issues = list_issues_in_milestone(repository, &timeframe)?;
 
         let teams_with_asks = teams_with_asks(&goal_documents);
         let mut actions = initialize_labels(repository, &teams_with_asks)?;
-        actions.extend(initialize_issues(repository, &timeframe, &goal_documents)?);
+        actions.extend(initialize_issues(
+            repository,
+            &timeframe,
+            &goal_documents,
+            &milestone_issues,
+        )?);
+        let rejected_issues =
+            rejected_issues(repository, &rejected_goal_documents, &milestone_issues)?;
+        actions.extend(close_issues(
+            &timeframe,
+            &goal_documents,
+            &rejected_issues,
+            &milestone_issues,
+        ));
+
+        // Write the plan even if it is empty, so the plan file always reflects the current state.
+        if let Some(plan_out) = plan_out {
+            let plan = IssuesPlan {
+                repository: repository.to_string(),
+                timeframe,
+                actions: actions.into_iter().collect(),
+            };
+            plan.write(plan_out)?;
+            eprintln!(
+                "Wrote {} actions to `{}`; use `--apply-plan` to execute them.",
+                plan.actions.len(),
+                plan_out.display()
+            );
+            return Ok(());
+        }
 
         if actions.is_empty() {
             return Ok(());
         }
 
         if commit {
-            progress_bar::init_progress_bar(actions.len());
-            progress_bar::set_progress_bar_action(
-                "Executing",
-                progress_bar::Color::Blue,
-                progress_bar::Style::Bold,
-            );
-            let mut success = 0;
-            for action in actions.into_iter() {
-                progress_bar::print_progress_bar_info(
-                    "Action",
-                    &format!("{}", action),
-                    progress_bar::Color::Green,
-                    progress_bar::Style::Bold,
-                );
-                if let Err(e) = action.execute(repository, &timeframe) {
-                    progress_bar::print_progress_bar_info(
-                        "Error",
-                        &format!("{}", e),
-                        progress_bar::Color::Red,
-                        progress_bar::Style::Bold,
-                    );
-                } else {
-                    success += 1;
-                }
-                progress_bar::inc_progress_bar();
-
-                std::thread::sleep(Duration::from_millis(sleep));
-            }
-            progress_bar::finalize_progress_bar();
-            if success == 0 {
-                spanned::bail_here!("all actions failed, aborting")
-            }
-
-            // Clear the cached issues since we just modified them
-            if let Err(e) = rust_project_goals::gh::issues::clear_milestone_issues_cache(&timeframe)
-            {
-                eprintln!("Warning: Failed to clear issues cache: {}", e);
-            }
+            execute_actions(repository, &timeframe, &goal_documents, actions, sleep)?;
         } else {
             eprintln!("Actions to be executed:");
             for action in &actions {
@@ -191,6 +202,131 @@ pub fn generate_issues(
     }
 }
 
+/// Execute the actions from a plan previously written with `--plan-out`,
+/// without recomputing them.
+fn execute_plan(
+    repository: &Repository,
+    path: &Path,
+    plan_path: &Path,
+    sleep: Duration,
+) -> Result<()> {
+    let timeframe = validate_path(path)?;
+    let plan = IssuesPlan::read(plan_path)?;
+
+    if plan.repository != repository.to_string() {
+        spanned::bail_here!(
+            "plan `{}` is for repository `{}`, not `{}`",
+            plan_path.display(),
+            plan.repository,
+            repository
+        );
+    }
+
+    if plan.timeframe != timeframe {
+        spanned::bail_here!(
+            "plan `{}` is for timeframe `{}`, not `{}`",
+            plan_path.display(),
+            plan.timeframe,
+            timeframe
+        );
+    }
+
+    if plan.actions.is_empty() {
+        eprintln!("Plan `{}` has no actions.", plan_path.display());
+        return Ok(());
+    }
+
+    let changed = changed_bodies(&plan.actions, |number| {
+        Ok(fetch_current_issue(repository, number)?.body)
+    })?;
+    if !changed.is_empty() {
+        spanned::bail_here!(
+            "the body of {} changed since plan `{}` was made; \
+             make a new plan with `--plan-out` and review it again",
+            changed
+                .iter()
+                .map(|number| format!("#{number}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            plan_path.display(),
+        );
+    }
+
+    // Needed to link newly created issues from their goal documents.
+    let goal_documents = goal::goals_in_dir(path)?;
+    execute_actions(repository, &timeframe, &goal_documents, plan.actions, sleep)?;
+
+    eprintln!("Plan executed; rerun without `--apply-plan` to check for follow-up actions.");
+    Ok(())
+}
+
+/// The issues whose body is updated by one of `actions`, but whose current body
+/// (as returned by `current_body`) is not the one the update was computed from.
+fn changed_bodies(
+    actions: &[GithubAction],
+    mut current_body: impl FnMut(u64) -> Result<String>,
+) -> Result<Vec<u64>> {
+    let mut changed = vec![];
+    for action in actions {
+        if let GithubAction::UpdateIssueBody {
+            number,
+            body_hash: expected_hash,
+            ..
+        } = action
+        {
+            if body_hash(&current_body(*number)?) != *expected_hash {
+                changed.push(*number);
+            }
+        }
+    }
+    Ok(changed)
+}
+
+fn execute_actions(
+    repository: &Repository,
+    timeframe: &str,
+    goal_documents: &[GoalDocument],
+    actions: impl IntoIterator<Item = GithubAction>,
+    sleep: Duration,
+) -> Result<()> {
+    let actions: Vec<GithubAction> = actions.into_iter().collect();
+
+    progress_bar::init_progress_bar(actions.len());
+    progress_bar::set_progress_bar_action(
+        "Executing",
+        progress_bar::Color::Blue,
+        progress_bar::Style::Bold,
+    );
+    let mut success = 0;
+    for action in actions.into_iter() {
+        progress_bar::print_progress_bar_info(
+            "Action",
+            &format!("{}", action),
+            progress_bar::Color::Green,
+            progress_bar::Style::Bold,
+        );
+        if let Err(e) = action.execute(repository, timeframe, goal_documents) {
+            progress_bar::print_progress_bar_info(
+                "Error",
+                &format!("{}", e),
+                progress_bar::Color::Red,
+                progress_bar::Style::Bold,
+            );
+        } else {
+            success += 1;
+        }
+        progress_bar::inc_progress_bar();
+
+        std::thread::sleep(sleep);
+    }
+    progress_bar::finalize_progress_bar();
+    if success == 0 {
+        spanned::bail_here!("all actions failed, aborting")
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GithubIssue<'doc> {
     pub title: String,
@@ -201,23 +337,53 @@ pub struct GithubIssue<'doc> {
     pub goal_document: &'doc GoalDocument,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum GithubAction<'doc> {
+/// An issue to create, as recorded in an [`IssuesPlan`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct NewIssue {
+    pub title: String,
+    pub assignees: BTreeSet<String>,
+    pub body: String,
+    pub labels: Vec<String>,
+
+    /// Path of the goal document, which gets updated to link to the new issue.
+    pub goal_document: PathBuf,
+}
+
+impl From<GithubIssue<'_>> for NewIssue {
+    fn from(issue: GithubIssue<'_>) -> Self {
+        NewIssue {
+            title: issue.title,
+            assignees: issue.assignees,
+            body: generated_region(&issue.body),
+            labels: issue.labels,
+            goal_document: issue.goal_document.path.clone(),
+        }
+    }
+}
+
+/// An action to take on github. The `before` fields record the state we observed
+/// when computing the action, so that a plan can be reviewed; they are not used
+/// when executing it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum GithubAction {
     CreateLabel {
         label: GhLabel,
     },
 
     CreateIssue {
-        issue: GithubIssue<'doc>,
+        issue: NewIssue,
     },
 
     ChangeTitle {
         number: u64,
+        before: String,
         title: String,
     },
 
     ChangeMilestone {
         number: u64,
+        before: Option<String>,
         milestone: String,
     },
 
@@ -226,20 +392,36 @@ enum GithubAction<'doc> {
         body: String,
     },
 
+    /// Bring the text `generated` from the goal document into the body of the issue.
+    /// Text that was edited on github is never overwritten: we only replace the generated
+    /// region if it is unchanged since we wrote it, and we keep the old text when replacing
+    /// a body that has no such region.
+    ///
+    /// The new body is computed again from the issue as it is on github when the action is
+    /// executed (see [`BodyUpdateReason::updated_body`]), so edits made in the meantime are kept.
     UpdateIssueBody {
         number: u64,
-        body: String,
+        generated: String,
+        reason: BodyUpdateReason,
+
+        /// Line diff from the body we saw to the new body, for review.
+        diff: String,
+
+        /// Hash of the body we saw (see [`body_hash`]). A plan is not applied if the body
+        /// changed since, as the diff that was reviewed would no longer be accurate.
+        body_hash: String,
     },
 
-    // We intentionally do not sync the issue *text*, because it may have been edited.
     SyncAssignees {
         number: u64,
+        before: BTreeSet<String>,
         remove_owners: BTreeSet<String>,
         add_owners: BTreeSet<String>,
     },
 
     SyncLabels {
         number: u64,
+        before: BTreeSet<String>,
         remove_labels: BTreeSet<String>,
         add_labels: BTreeSet<String>,
     },
@@ -247,6 +429,58 @@ enum GithubAction<'doc> {
     LockIssue {
         number: u64,
     },
+
+    /// Post `comment` explaining why we close the issue, then close it.
+    CloseIssue {
+        number: u64,
+        comment: String,
+        state_reason: Option<IssueStateReason>,
+    },
+}
+
+/// Why we replace the body of an existing tracking issue.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyUpdateReason {
+    /// The goal document changed since we generated the text between the markers.
+    GoalChanged,
+
+    /// The body was generated for this goal before we added the markers; the text we
+    /// generated is replaced by a marked region, keeping the rest of the body.
+    AddMarkers,
+
+    /// The body has no generated region and describes the goal of an earlier milestone;
+    /// the old text is kept below the new one.
+    NewMilestone,
+}
+
+impl BodyUpdateReason {
+    /// The body that replaces `existing_body` to bring in the text `generated` from the
+    /// goal document of `timeframe`, or `None` if the body is no longer in a state where
+    /// this applies (e.g., the generated region was edited on github).
+    fn updated_body(self, existing_body: &str, generated: &str, timeframe: &str) -> Option<String> {
+        match self {
+            BodyUpdateReason::GoalChanged => match sync_body(existing_body, generated) {
+                BodySync::Update(body) => Some(body),
+                BodySync::UpToDate | BodySync::Drifted | BodySync::NoRegion => None,
+            },
+
+            BodyUpdateReason::AddMarkers => adopt_body(existing_body, generated),
+
+            BodyUpdateReason::NewMilestone => match sync_body(existing_body, generated) {
+                // Let's update the tracking issue to the new goal description, while keeping
+                // the old text in case we need it. It's surprisingly hard to get out of GH
+                // otherwise.
+                BodySync::NoRegion => Some(format!(
+                    "{desired_body}\n---\nNote: we have updated the body to match the \
+                     {timeframe} goal. Your original text is preserved below. \
+                     <details>\n{existing_body}\n</details>",
+                    desired_body = generated_region(generated),
+                )),
+                BodySync::UpToDate | BodySync::Update(_) | BodySync::Drifted => None,
+            },
+        }
+    }
 }
 
 /// Initializes the required `T-<team>` labels on the repository.
@@ -254,7 +488,7 @@ enum GithubAction<'doc> {
 fn initialize_labels(
     repository: &Repository,
     teams_with_asks: &BTreeSet<&TeamName>,
-) -> Result<BTreeSet<GithubAction<'static>>> {
+) -> Result<BTreeSet<GithubAction>> {
     const TEAM_LABEL_COLOR: &str = "bfd4f2";
 
     let mut desired_labels: BTreeSet<_> = teams_with_asks
@@ -291,11 +525,12 @@ fn initialize_labels(
 
 /// Initializes the required `T-<team>` labels on the repository.
 /// Warns if the labels are found with wrong color.
-fn initialize_issues<'doc>(
+fn initialize_issues(
     repository: &Repository,
     timeframe: &str,
-    goal_documents: &'doc [GoalDocument],
-) -> Result<BTreeSet<GithubAction<'doc>>> {
+    goal_documents: &[GoalDocument],
+    milestone_issues: &[ExistingGithubIssue],
+) -> Result<BTreeSet<GithubAction>> {
     // the set of issues we want to exist
     let desired_issues: BTreeSet<GithubIssue> = goal_documents
         .iter()
@@ -331,9 +566,6 @@ fn initialize_issues<'doc>(
         }
     }
 
-    // the list of existing issues in the target milestone
-    let milestone_issues = list_issues_in_milestone(repository, timeframe)?;
-
     let mut actions = BTreeSet::new();
 
     // Go through each of the issues we want to exist (derived from the goals defined in the target folder)
@@ -382,6 +614,7 @@ fn initialize_issues<'doc>(
                 if existing_issue.assignees != desired_issue.assignees {
                     actions.insert(GithubAction::SyncAssignees {
                         number: existing_issue.number,
+                        before: existing_issue.assignees.clone(),
                         remove_owners: existing_issue
                             .assignees
                             .difference(&desired_issue.assignees)
@@ -407,6 +640,7 @@ fn initialize_issues<'doc>(
                 if existing_label_names != desired_label_names {
                     actions.insert(GithubAction::SyncLabels {
                         number: existing_issue.number,
+                        before: existing_label_names.clone(),
                         remove_labels: existing_label_names
                             .difference(&desired_label_names)
                             .cloned()
@@ -421,6 +655,7 @@ fn initialize_issues<'doc>(
                 if existing_issue.title != desired_issue.title {
                     actions.insert(GithubAction::ChangeTitle {
                         number: existing_issue.number,
+                        before: existing_issue.title.clone(),
                         title: desired_issue.title,
                     });
                 }
@@ -428,6 +663,7 @@ fn initialize_issues<'doc>(
                 if existing_issue.milestone.as_ref().map(|m| m.title.as_str()) != Some(timeframe) {
                     actions.insert(GithubAction::ChangeMilestone {
                         number: existing_issue.number,
+                        before: existing_issue.milestone.as_ref().map(|m| m.title.clone()),
                         milestone: timeframe.to_string(),
                     });
                     actions.insert(GithubAction::Comment {
@@ -446,28 +682,62 @@ fn initialize_issues<'doc>(
                     });
                 }
 
-                let link_text = goal_document_link(timeframe, &desired_issue.goal_document);
-                if !existing_issue.body.contains(&link_text) {
-                    // Let's update the tracking issue to the new goal description, while keeping
-                    // the old text in case we need it. It's surprisingly hard to get out of GH
-                    // otherwise.
-                    let body = format!(
-                        "{desired_body}\n---\nNote: we have updated the body to match the \
-                         {timeframe} goal. Your original text is preserved below. \
-                         <details>\n{existing_body}\n</details>",
-                        desired_body = desired_issue.body,
-                        existing_body = existing_issue.body,
-                    );
-                    actions.insert(GithubAction::UpdateIssueBody {
-                        number: existing_issue.number,
-                        body,
-                    });
+                let reason = match sync_body(&existing_issue.body, &desired_issue.body) {
+                    BodySync::UpToDate => None,
+
+                    BodySync::Update(_) => Some(BodyUpdateReason::GoalChanged),
+
+                    BodySync::Drifted => {
+                        eprintln!(
+                            "Warning: the generated text in issue #{} was edited on github, \
+                             so it is not updated to match `{}`; revert the edits between \
+                             the markers to resume syncing.",
+                            existing_issue.number,
+                            desired_issue.goal_document.path.display(),
+                        );
+                        None
+                    }
+
+                    BodySync::NoRegion => {
+                        let link_text = goal_document_link(timeframe, &desired_issue.goal_document);
+                        if existing_issue.body.contains(&link_text) {
+                            // The issue was created for this goal by an older version of this
+                            // tool; mark the text we generated so that it is synced from now on.
+                            Some(BodyUpdateReason::AddMarkers)
+                        } else {
+                            Some(BodyUpdateReason::NewMilestone)
+                        }
+                    }
+                };
+
+                if let Some(reason) = reason {
+                    match reason.updated_body(&existing_issue.body, &desired_issue.body, timeframe)
+                    {
+                        Some(body) => {
+                            actions.insert(GithubAction::UpdateIssueBody {
+                                number: existing_issue.number,
+                                generated: desired_issue.body.clone(),
+                                reason,
+                                diff: line_diff(&existing_issue.body, &body),
+                                body_hash: body_hash(&existing_issue.body),
+                            });
+                        }
+                        None => {
+                            eprintln!(
+                                "Warning: issue #{} links to `{}`, but its body does not \
+                                 contain the text we generate, so it is not synced; add \
+                                 the generated region by hand to start syncing.",
+                                existing_issue.number,
+                                desired_issue.goal_document.path.display(),
+                            );
+                        }
+                    }
                 }
             }
 
             None => {
                 actions.insert(GithubAction::CreateIssue {
-                    issue: desired_issue,
+                    issue: desired_issue.into(),
                 });
             }
         }
@@ -476,6 +746,111 @@ fn initialize_issues<'doc>(
     Ok(actions)
 }
 
+/// The tracking issues of the goals in `rejected_goal_documents`, taken from
+/// `milestone_issues` where possible and fetched otherwise.
+fn rejected_issues(
+    repository: &Repository,
+    rejected_goal_documents: &[GoalDocument],
+    milestone_issues: &[ExistingGithubIssue],
+) -> Result<Vec<ExistingGithubIssue>> {
+    let mut issues = vec![];
+    for document in rejected_goal_documents {
+        let Some(tracking_issue) = &document.metadata.tracking_issue else {
+            continue;
+        };
+
+        let issue = match milestone_issues
+            .iter()
+            .find(|issue| issue.number == tracking_issue.number)
+        {
+            Some(issue) => issue.clone(),
+            None => fetch_issue(repository, tracking_issue.number)?,
+        };
+        issues.push(issue);
+    }
+    Ok(issues)
+}
+
+/// Closes the tracking issues of goals that were not accepted (see [`rejected_issues`]),
+/// as well as tracking issues in the milestone whose goal document is gone.
+///
+/// Only issues in the `timeframe` milestone are closed: the tracking issue of a rejected
+/// goal may still belong to the milestone the goal is continued from, so it is only reported.
+fn close_issues(
+    timeframe: &str,
+    goal_documents: &[GoalDocument],
+    rejected_issues: &[ExistingGithubIssue],
+    milestone_issues: &[ExistingGithubIssue],
+) -> BTreeSet<GithubAction> {
+    let mut actions = BTreeSet::new();
+
+    for issue in rejected_issues {
+        if issue.state != GithubIssueState::Open {
+            continue;
+        }
+
+        if issue.milestone.as_ref().map(|m| m.title.as_str()) != Some(timeframe) {
+            eprintln!(
+                "warning: not closing #{} although its goal was not accepted for {timeframe}, \
+                since it is not in the {timeframe} milestone",
+                issue.number,
+            );
+            continue;
+        }
+
+        actions.insert(GithubAction::CloseIssue {
+            number: issue.number,
+            comment: format!("{CLOSING_GOAL_PREFIX} the goal was not accepted for {timeframe}."),
+            state_reason: Some(IssueStateReason::NotPlanned),
+        });
+    }
+
+    for issue in milestone_issues {
+        if issue.state != GithubIssueState::Open
+            || !issue.has_label("C-tracking-issue")
+            || rejected_issues
+                .iter()
+                .any(|rejected| rejected.number == issue.number)
+        {
+            continue;
+        }
+
+        // Match issues to goals the same way as `initialize_issues`.
+        let has_goal =
+            goal_documents
+                .iter()
+                .any(|document| match &document.metadata.tracking_issue {
+                    Some(tracking_issue) => tracking_issue.number == issue.number,
+                    None => document.metadata.title.to_string() == issue.title,
+                });
+        if has_goal {
+            continue;
+        }
+
+        // If the issue tracks its progress, we can tell whether the goal was completed.
+        let state_reason = match checkboxes(issue) {
+            Progress::Tracked { completed, total }
+            | Progress::Weighted {
+                completed, total, ..
+            } if completed == total => Some(IssueStateReason::Completed),
+            Progress::Tracked { .. } | Progress::Weighted { .. } => {
+                Some(IssueStateReason::NotPlanned)
+            }
+            Progress::Binary { .. } | Progress::Error { .. } => None,
+        };
+
+        actions.insert(GithubAction::CloseIssue {
+            number: issue.number,
+            comment: format!(
+                "{CLOSING_GOAL_PREFIX} the goal is no longer part of the {timeframe} milestone."
+            ),
+            state_reason,
+        });
+    }
+
+    actions
+}
+
 fn issue<'doc>(timeframe: &str, document: &'doc GoalDocument) -> Result<GithubIssue<'doc>> {
     let mut assignees = BTreeSet::default();
     for username in document.metadata.owner_usernames() {
@@ -588,7 +963,7 @@ fn teams_with_asks(goal_documents: &[GoalDocument]) -> BTreeSet<&'static TeamNam
         .collect()
 }
 
-impl Display for GithubAction<'_> {
+impl Display for GithubAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GithubAction::CreateLabel {
@@ -599,20 +974,65 @@ impl Display for GithubAction<'_> {
             GithubAction::CreateIssue { issue } => {
                 write!(f, "create issue \"{}\"", issue.title,)
             }
-            GithubAction::ChangeMilestone { number, milestone } => {
+            GithubAction::ChangeMilestone {
+                number,
+                before: _,
+                milestone,
+            } => {
                 write!(f, "update issue #{} milestone to \"{}\"", number, milestone)
             }
-            GithubAction::ChangeTitle { number, title } => {
+            GithubAction::ChangeTitle {
+                number,
+                before: _,
+                title,
+            } => {
                 write!(f, "update issue #{} title to \"{}\"", number, title)
             }
             GithubAction::Comment { number, body } => {
                 write!(f, "post comment on issue #{}: \"{}\"", number, body)
             }
-            GithubAction::UpdateIssueBody { number, body: _ } => {
-                write!(f, "update the body on issue #{} for new milestone", number)
+            GithubAction::UpdateIssueBody {
+                number,
+                generated: _,
+                reason: BodyUpdateReason::GoalChanged,
+                diff: _,
+                body_hash: _,
+            } => {
+                write!(
+                    f,
+                    "update the generated text on issue #{} to match the goal document",
+                    number
+                )
+            }
+            GithubAction::UpdateIssueBody {
+                number,
+                generated: _,
+                reason: BodyUpdateReason::AddMarkers,
+                diff: _,
+                body_hash: _,
+            } => {
+                write!(
+                    f,
+                    "mark the generated text on issue #{} so that it is kept in sync",
+                    number
+                )
+            }
+            GithubAction::UpdateIssueBody {
+                number,
+                generated: _,
+                reason: BodyUpdateReason::NewMilestone,
+                diff: _,
+                body_hash: _,
+            } => {
+                write!(
+                    f,
+                    "replace the body on issue #{} for new milestone, keeping the old text",
+                    number
+                )
             }
             GithubAction::SyncAssignees {
                 number,
+                before: _,
                 remove_owners,
                 add_owners,
             } => {
@@ -630,6 +1050,7 @@ impl Display for GithubAction<'_> {
             }
             GithubAction::SyncLabels {
                 number,
+                before: _,
                 remove_labels,
                 add_labels,
             } => {
@@ -648,12 +1069,32 @@ impl Display for GithubAction<'_> {
             GithubAction::LockIssue { number } => {
                 write!(f, "lock issue #{}", number)
             }
+            GithubAction::CloseIssue {
+                number,
+                comment: _,
+                state_reason: Some(state_reason),
+            } => {
+                write!(f, "close issue #{} as {}", number, state_reason)
+            }
+            GithubAction::CloseIssue {
+                number,
+                comment: _,
+                state_reason: None,
+            } => {
+                write!(f, "close issue #{}", number)
+            }
         }
     }
 }
 
-impl GithubAction<'_> {
-    pub fn execute(self, repository: &Repository, timeframe: &str) -> Result<()> {
+impl GithubAction {
+    /// Execute the action; `goal_documents` are the documents the actions were computed from.
+    pub fn execute(
+        self,
+        repository: &Repository,
+        timeframe: &str,
+        goal_documents: &[GoalDocument],
+    ) -> Result<()> {
         match self {
             GithubAction::CreateLabel { label } => {
                 label.create(repository)?;
@@ -662,15 +1103,22 @@ impl GithubAction<'_> {
 
             GithubAction::CreateIssue {
                 issue:
-                    GithubIssue {
+                    NewIssue {
                         title,
                         assignees,
                         body,
                         labels,
-                        tracking_issue: _,
                         goal_document,
                     },
             } => {
+                let Some(goal_document) = goal_documents.iter().find(|d| d.path == goal_document)
+                else {
+                    spanned::bail_here!(
+                        "no goal document at `{}` to link the new issue from",
+                        goal_document.display()
+                    );
+                };
+
                 let issue_id =
                     create_issue(repository, &body, &title, &labels, &assignees, timeframe)?;
 
@@ -679,12 +1127,20 @@ impl GithubAction<'_> {
                 Ok(())
             }
 
-            GithubAction::ChangeMilestone { number, milestone } => {
+            GithubAction::ChangeMilestone {
+                number,
+                before: _,
+                milestone,
+            } => {
                 change_milestone(repository, number, &milestone)?;
                 Ok(())
             }
 
-            GithubAction::ChangeTitle { number, title } => {
+            GithubAction::ChangeTitle {
+                number,
+                before: _,
+                title,
+            } => {
                 change_title(repository, number, &title)?;
                 Ok(())
             }
@@ -694,13 +1150,29 @@ impl GithubAction<'_> {
                 Ok(())
             }
 
-            GithubAction::UpdateIssueBody { number, body } => {
-                update_issue_body(repository, number, &body)?;
+            GithubAction::UpdateIssueBody {
+                number,
+                generated,
+                reason,
+                diff: _,
+                body_hash: _,
+            } => {
+                // The body may have changed since we computed the action (or since the copy
+                // of the issue we computed it from was cached), so start over from the current one.
+                let issue = fetch_current_issue(repository, number)?;
+                match reason.updated_body(&issue.body, &generated, timeframe) {
+                    Some(body) => update_issue_body(repository, number, &body)?,
+                    None => eprintln!(
+                        "Warning: not updating the body of issue #{number}, \
+                         since it changed on github in the meantime"
+                    ),
+                }
                 Ok(())
             }
 
             GithubAction::SyncAssignees {
                 number,
+                before: _,
                 remove_owners,
                 add_owners,
             } => {
@@ -712,6 +1184,7 @@ impl GithubAction<'_> {
 
             GithubAction::SyncLabels {
                 number,
+                before: _,
                 remove_labels,
                 add_labels,
             } => {
@@ -720,6 +1193,127 @@ impl GithubAction<'_> {
             }
 
             GithubAction::LockIssue { number } => lock_issue(repository, number),
+
+            GithubAction::CloseIssue {
+                number,
+                comment,
+                state_reason,
+            } => {
+                create_comment(repository, number, &comment)?;
+                close_issue(repository, number, state_reason)?;
+                Ok(())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_project_goals::gh::milestone::GhMilestone;
+
+    fn tracking_issue(number: u64, milestone: &str, body: &str) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number,
+            title: format!("Goal {number}"),
+            assignees: Default::default(),
+            comments: vec![],
+            body: body.to_string(),
+            state: GithubIssueState::Open,
+            labels: vec![GhLabel {
+                name: "C-tracking-issue".to_string(),
+                color: String::new(),
+            }],
+            milestone: Some(GhMilestone {
+                number: 1,
+                title: milestone.to_string(),
+                description: String::new(),
+                due_on: None,
+            }),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_close_issues() {
+        // A rejected goal continued from 2025h2 keeps its issue open in that milestone.
+        let rejected_issues = [
+            tracking_issue(1, "2026", ""),
+            tracking_issue(2, "2025h2", ""),
+        ];
+        let milestone_issues = [
+            rejected_issues[0].clone(),
+            tracking_issue(3, "2026", "- [x] Land the parser\n- [x] Ship it\n"),
+            tracking_issue(4, "2026", "- [x] Land the parser\n- [ ] Ship it\n"),
+            tracking_issue(5, "2026", "No checkboxes here.\n"),
+        ];
+
+        let closed: Vec<(u64, Option<IssueStateReason>)> =
+            close_issues("2026", &[], &rejected_issues, &milestone_issues)
+                .into_iter()
+                .map(|action| match action {
+                    GithubAction::CloseIssue {
+                        number,
+                        state_reason,
+                        ..
+                    } => (number, state_reason),
+                    action => panic!("unexpected action: {action:?}"),
+                })
+                .collect();
+        assert_eq!(
+            closed,
+            vec![
+                (1, Some(IssueStateReason::NotPlanned)),
+                (3, Some(IssueStateReason::Completed)),
+                (4, Some(IssueStateReason::NotPlanned)),
+                (5, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_updated_body_from_current_body() {
+        // Notes added on github after the action was computed are kept.
+        let current = format!("{}\n\nNotes added later.", generated_region("* [ ] one"));
+        assert_eq!(
+            BodyUpdateReason::GoalChanged.updated_body(&current, "* [ ] two", "2026"),
+            Some(format!(
+                "{}\n\nNotes added later.",
+                generated_region("* [ ] two")
+            ))
+        );
+
+        // A generated region that was edited in the meantime is left alone.
+        let edited = current.replace("one", "one (edited)");
+        assert_eq!(
+            BodyUpdateReason::GoalChanged.updated_body(&edited, "* [ ] two", "2026"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_changed_bodies() {
+        let update = |number: u64, body: &str| GithubAction::UpdateIssueBody {
+            number,
+            generated: "* [ ] two".to_string(),
+            reason: BodyUpdateReason::GoalChanged,
+            diff: String::new(),
+            body_hash: body_hash(body),
+        };
+        let actions = [
+            update(1, "unchanged"),
+            GithubAction::LockIssue { number: 2 },
+            update(3, "before"),
+        ];
+
+        let changed = changed_bodies(&actions, |number| {
+            Ok(match number {
+                1 => "unchanged".to_string(),
+                3 => "edited on github".to_string(),
+                _ => panic!("fetched issue #{number}"),
+            })
+        })
+        .unwrap();
+        assert_eq!(changed, vec![3]);
+    }
+}
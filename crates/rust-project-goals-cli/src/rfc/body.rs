@@ -0,0 +1,310 @@
+//! Tracking issue bodies keep the text we generate from the goal document between two
+//! marker comments. When the goal document changes, we regenerate just that region and
+//! leave anything the owners wrote outside of the markers alone.
+//!
+//! The begin marker records a hash of the text we generated, which lets us tell
+//! whether someone edited the region by hand since (in which case we do not overwrite it).
+
+use std::ops::Range;
+
+use rust_project_goals::re;
+
+const BEGIN_MARKER_PREFIX: &str = "<!-- rust-project-goals:begin";
+const END_MARKER: &str = "<!-- rust-project-goals:end -->";
+
+/// The last line of the issue text we generate (see `issue_text`), also in bodies
+/// generated before we added the markers.
+const TEAM_ASK_LINK: &str = "[Team]: https://img.shields.io/badge/Team%20ask-red";
+
+/// The rows of the metadata table that we generate. Owners add others, like `Tracked issues`.
+const GENERATED_METADATA_ROWS: &[&str] =
+    &["Metadata", "Point of contact", "Team(s)", "Goal document"];
+
+/// Wrap text generated from the goal document in markers so that it can be synced later.
+pub fn generated_region(text: &str) -> String {
+    format!(
+        "{BEGIN_MARKER_PREFIX} hash={hash} -->\n{text}\n{END_MARKER}",
+        hash = hash(&normalize(text)),
+    )
+}
+
+/// What to do with the body of an existing tracking issue.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BodySync {
+    /// The generated region matches the goal document.
+    UpToDate,
+
+    /// The generated region is out of date; replace the body with this text.
+    Update(String),
+
+    /// The goal document changed, but so did the generated region on github,
+    /// so we leave it alone rather than overwrite someone's edits.
+    Drifted,
+
+    /// The body has no generated region (e.g., it was created by an older version of this tool).
+    NoRegion,
+}
+
+/// Decide how to update `existing_body` so its generated region reflects `generated`
+/// (the freshly generated issue text). This is a three-way comparison between the text we
+/// generated last time (known via its hash), the region as it is now, and the new text.
+/// Boxes checked on github stay checked, since that is how owners report progress.
+pub fn sync_body(existing_body: &str, generated: &str) -> BodySync {
+    let Some(region) = find_region(existing_body) else {
+        return BodySync::NoRegion;
+    };
+
+    // Nothing changed in the goal document since we last generated the region.
+    if hash(&normalize(generated)) == region.hash {
+        return BodySync::UpToDate;
+    }
+
+    if hash(&normalize(region.text)) != region.hash {
+        return BodySync::Drifted;
+    }
+
+    let body = format!(
+        "{before}{region}{after}",
+        before = &existing_body[..region.range.start],
+        region = generated_region(&keep_checked_boxes(region.text, generated)),
+        after = &existing_body[region.range.end..],
+    );
+
+    if body == existing_body {
+        BodySync::UpToDate
+    } else {
+        BodySync::Update(body)
+    }
+}
+
+/// Brings the body of an issue that was generated before we added the markers under sync.
+/// The text we generated back then (from the metadata table up to the `[Team]` link
+/// definition) is replaced by a generated region with `generated`, keeping the boxes that
+/// were checked. The text before and after it is kept, and so are the rows that the owners
+/// added to the metadata table, which move into a table of their own after the region.
+///
+/// Returns `None` if the body already has a generated region, or if it has no text that
+/// looks like ours.
+pub fn adopt_body(existing_body: &str, generated: &str) -> Option<String> {
+    if find_region(existing_body).is_some() {
+        return None;
+    }
+
+    let start = find_line(existing_body, "| Metadata")?;
+    let end = start + existing_body[start..].find(TEAM_ASK_LINK)? + TEAM_ASK_LINK.len();
+    let old_text = &existing_body[start..end];
+
+    let mut region = generated_region(&keep_checked_boxes(old_text, generated));
+    let added_rows: Vec<&str> = old_text
+        .lines()
+        .take_while(|line| line.starts_with('|'))
+        .filter(|line| !is_generated_row(line))
+        .collect();
+    if !added_rows.is_empty() {
+        region.push_str("\n\n| Metadata | |\n| -------- | --- |\n");
+        region.push_str(&added_rows.join("\n"));
+    }
+
+    Some(format!(
+        "{before}{region}{after}",
+        before = &existing_body[..start],
+        after = &existing_body[end..],
+    ))
+}
+
+/// The offset of the first line of `body` that starts with `prefix`.
+fn find_line(body: &str, prefix: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        if line.starts_with(prefix) {
+            return Some(offset);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// True if `line` is the header, the separator, or one of the rows of the metadata table we generate.
+fn is_generated_row(line: &str) -> bool {
+    let key = line
+        .trim_start_matches('|')
+        .split('|')
+        .next()
+        .unwrap_or("")
+        .trim();
+    key.chars().all(|c| c == '-' || c == ':') || GENERATED_METADATA_ROWS.contains(&key)
+}
+
+struct Region<'b> {
+    /// Byte range of the region in the body, including the markers.
+    range: Range<usize>,
+    hash: &'b str,
+    text: &'b str,
+}
+
+fn find_region(body: &str) -> Option<Region<'_>> {
+    let start = body.find(BEGIN_MARKER_PREFIX)?;
+    let attributes_start = start + BEGIN_MARKER_PREFIX.len();
+    let attributes_end = attributes_start + body[attributes_start..].find("-->")?;
+    let hash = body[attributes_start..attributes_end]
+        .trim()
+        .strip_prefix("hash=")?;
+
+    let text_start = attributes_end + "-->".len();
+    let text_end = text_start + body[text_start..].find(END_MARKER)?;
+    let text = &body[text_start..text_end];
+    let text = text
+        .strip_prefix("\r\n")
+        .or(text.strip_prefix('\n'))
+        .unwrap_or(text);
+    let text = text
+        .strip_suffix("\r\n")
+        .or(text.strip_suffix('\n'))
+        .unwrap_or(text);
+
+    Some(Region {
+        range: start..text_end + END_MARKER.len(),
+        hash,
+        text,
+    })
+}
+
+/// If `line` is a checkbox item, returns whether it is checked and the item text.
+fn checkbox(line: &str) -> Option<(bool, &str)> {
+    let m = re::CHECKBOX.find(line).filter(|m| m.start() == 0)?;
+    Some((re::CHECKED_CHECKBOX.is_match(m.as_str()), &line[m.end()..]))
+}
+
+/// The text with all boxes unchecked and github's `\r\n` line endings replaced,
+/// so that checking boxes does not count as editing the generated region.
+fn normalize(text: &str) -> String {
+    text.lines()
+        .map(|line| match checkbox(line) {
+            Some((true, _)) => line.replacen("[x]", "[ ]", 1),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Check the boxes in `generated` whose items are checked in `current`.
+fn keep_checked_boxes(current: &str, generated: &str) -> String {
+    let checked: Vec<&str> = current
+        .lines()
+        .filter_map(checkbox)
+        .filter(|&(is_checked, _)| is_checked)
+        .map(|(_, text)| text)
+        .collect();
+
+    generated
+        .lines()
+        .map(|line| match checkbox(line) {
+            Some((false, text)) if checked.contains(&text) => line.replacen("[ ]", "[x]", 1),
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A hash of the whole body of an issue, to tell whether it changed since we saw it.
+pub fn body_hash(body: &str) -> String {
+    hash(body)
+}
+
+/// 64-bit FNV-1a. We store these hashes in issue bodies, so unlike `DefaultHasher`
+/// the result must not change between Rust versions.
+fn hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(region: &str) -> String {
+        format!("Owner notes above.\n\n{region}\n\nOwner notes below.")
+    }
+
+    #[test]
+    fn test_sync_body_up_to_date() {
+        let generated = "## Tasks\n\n* [ ] one\n* [ ] two";
+        let existing = body(&generated_region(generated));
+        assert_eq!(sync_body(&existing, generated), BodySync::UpToDate);
+
+        // Checking a box on github is not an edit.
+        let existing = existing.replace("* [ ] one", "* [x] one");
+        assert_eq!(sync_body(&existing, generated), BodySync::UpToDate);
+    }
+
+    #[test]
+    fn test_sync_body_update_keeps_checked_boxes() {
+        let old = "## Tasks\n\n* [ ] one\n* [ ] two";
+        let existing = body(&generated_region(old)).replace("* [ ] one", "* [x] one");
+
+        let new = "## Tasks\n\n* [ ] one\n* [ ] three";
+        let BodySync::Update(updated) = sync_body(&existing, new) else {
+            panic!("expected an update");
+        };
+        assert_eq!(
+            updated,
+            body(&generated_region("## Tasks\n\n* [x] one\n* [ ] three"))
+        );
+    }
+
+    #[test]
+    fn test_sync_body_drifted() {
+        let old = "## Tasks\n\n* [ ] one";
+        let existing = body(&generated_region(old)).replace("one", "one (edited)");
+        assert_eq!(
+            sync_body(&existing, "## Tasks\n\n* [ ] two"),
+            BodySync::Drifted
+        );
+    }
+
+    #[test]
+    fn test_sync_body_github_line_endings() {
+        let generated = "## Tasks\n\n* [ ] one";
+        let existing = body(&generated_region(generated)).replace('\n', "\r\n");
+        assert_eq!(sync_body(&existing, generated), BodySync::UpToDate);
+    }
+
+    #[test]
+    fn test_sync_body_no_region() {
+        assert_eq!(sync_body("hand written", "generated"), BodySync::NoRegion);
+    }
+
+    #[test]
+    fn test_adopt_body() {
+        let tasks = "## Tasks and status\n\n* [ ] one\n* [ ] two\n\n[Team]: https://img.shields.io/badge/Team%20ask-red";
+        let tracked = "| Tracked issues   | [rust-lang/rust label:A-foo](https://github.com/rust-lang/rust/labels/A-foo) |";
+        let old = format!(
+            "| Metadata         | |\n| --------         | --- |\n| Point of contact | @alice |\n\
+            {tracked}\n\n{tasks}"
+        )
+        .replace("* [ ] one", "* [x] one");
+        let existing = body(&old);
+
+        let generated = format!(
+            "| Metadata         | |\n| --------         | --- |\n| Point of contact | @bob |\n\n{tasks}"
+        );
+        let adopted = adopt_body(&existing, &generated).unwrap();
+        assert_eq!(
+            adopted,
+            body(&format!(
+                "{region}\n\n| Metadata | |\n| -------- | --- |\n{tracked}",
+                region = generated_region(&generated.replace("* [ ] one", "* [x] one")),
+            ))
+        );
+
+        // From then on, the body is kept in sync.
+        assert_eq!(sync_body(&adopted, &generated), BodySync::UpToDate);
+        assert_eq!(adopt_body(&adopted, &generated), None);
+
+        assert_eq!(adopt_body("hand written", &generated), None);
+    }
+}
@@ -0,0 +1,81 @@
+use std::{fmt::Write, path::Path};
+
+use rust_project_goals::spanned::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::GithubAction;
+
+/// The actions computed by `cargo rpg issues`, in a form that can be written out with
+/// `--plan-out`, reviewed (e.g., in a PR), and later executed exactly with `--apply-plan`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssuesPlan {
+    /// Repository the actions apply to (e.g., `rust-lang/rust-project-goals`).
+    pub repository: String,
+
+    /// Milestone of the goals (e.g., `2025h2`).
+    pub timeframe: String,
+
+    pub actions: Vec<GithubAction>,
+}
+
+impl IssuesPlan {
+    pub fn read(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).with_path_context(path, "reading plan")?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text).with_path_context(path, "writing plan")?;
+        Ok(())
+    }
+}
+
+/// A line-by-line diff from `before` to `after`, where each line is prefixed with
+/// ` ` (unchanged), `-` (removed), or `+` (added). Used to review body changes in a plan.
+pub fn line_diff(before: &str, after: &str) -> String {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `before[i..]` and `after[j..]`.
+    let mut lcs = vec![vec![0; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut output = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            writeln!(output, " {}", before[i]).unwrap();
+            i += 1;
+            j += 1;
+        } else if i < before.len() && (j == after.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            writeln!(output, "-{}", before[i]).unwrap();
+            i += 1;
+        } else {
+            writeln!(output, "+{}", after[j]).unwrap();
+            j += 1;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff() {
+        assert_eq!(line_diff("a\nb\nc", "a\nc\nd"), " a\n-b\n c\n+d\n");
+        assert_eq!(line_diff("", "a"), "+a\n");
+        assert_eq!(line_diff("a\nb", "a\nb"), " a\n b\n");
+        assert_eq!(line_diff("old", "new"), "-old\n+new\n");
+    }
+}
@@ -0,0 +1,270 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use rust_project_goals::{
+    goal::{self, GoalDocument},
+    spanned::{self, Context as _, Result},
+    util::format_table,
+};
+
+use crate::cfp::validate_timeframe;
+use crate::migrate::{self, Edit};
+
+/// Copies the goals from milestone `from` that continue into milestone `to`.
+///
+/// Continues exactly the goals named in `goals` (by file name, e.g. `cargo-plumbing`) if there
+/// are any, all accepted goals with `yes`, and otherwise asks about each goal in turn.
+/// The copies keep their tracking issue, have their status reset to `Proposed`, and are
+/// converted to the 2026 "Team asks" format.
+pub fn rollover(
+    from: &str,
+    to: &str,
+    goals: &[String],
+    yes: bool,
+    force: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if dry_run {
+        println!("Dry run mode - no changes will be made");
+    }
+
+    validate_timeframe(from)?;
+    validate_timeframe(to)?;
+
+    let from_dir = PathBuf::from("src").join(from.to_lowercase());
+    let to_dir = PathBuf::from("src").join(to.to_lowercase());
+    if !to_dir.exists() {
+        spanned::bail_here!(
+            "directory {} does not exist; run `cargo rpg cfp {to}` first",
+            to_dir.display()
+        );
+    }
+
+    let mut goal_documents = goal::goals_in_dir(&from_dir)?;
+    goal_documents.retain(|gd| gd.is_not_not_accepted());
+
+    for name in goals {
+        if !goal_documents.iter().any(|gd| file_stem(gd) == name) {
+            spanned::bail_here!("no goal named `{name}` in {}", from_dir.display());
+        }
+    }
+
+    let names: Vec<&str> = goal_documents.iter().map(file_stem).collect();
+    let selected = select_goals(&names, goals, yes, |index| {
+        ask_continue(&goal_documents[index], to)
+    })?;
+
+    let mut continued = 0;
+    for goal_document in selected.into_iter().map(|index| &goal_documents[index]) {
+        let dest_path = to_dir.join(goal_document.path.file_name().unwrap());
+        if dest_path.exists() && !force {
+            println!(
+                "Skipping {}: {} already exists (use --force to overwrite)",
+                goal_document.path.display(),
+                dest_path.display()
+            );
+            continue;
+        }
+
+        let text = continued_goal_text(goal_document)?;
+        if dry_run {
+            println!("Would create file: {}", dest_path.display());
+        } else {
+            std::fs::write(&dest_path, text)
+                .with_path_context(&dest_path, "Failed to write to file")?;
+            println!("Created file: {}", dest_path.display());
+        }
+        continued += 1;
+    }
+
+    println!("\n{continued} goal(s) continued from {from} into {to}.");
+    if continued > 0 {
+        println!("\nNext steps:");
+        println!(
+            "1. Review the support levels in the `Team asks` sections, which are only suggestions"
+        );
+        println!("2. Ask the point of contact of each goal to update the text for {to}");
+        println!(
+            "3. Once the goals are accepted, run `cargo rpg issues {}` to move their tracking issues",
+            to_dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+fn file_stem(goal_document: &GoalDocument) -> &str {
+    goal_document
+        .path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+}
+
+/// The indices of the goals (named by their file stem in `names`) to continue: those named
+/// in `goals` if there are any, all of them with `yes`, and otherwise those for which `ask`
+/// returns true.
+fn select_goals(
+    names: &[&str],
+    goals: &[String],
+    yes: bool,
+    mut ask: impl FnMut(usize) -> Result<bool>,
+) -> Result<Vec<usize>> {
+    let mut selected = vec![];
+    for (index, name) in names.iter().enumerate() {
+        let continues = if !goals.is_empty() {
+            goals.iter().any(|goal| goal == name)
+        } else {
+            yes || ask(index)?
+        };
+        if continues {
+            selected.push(index);
+        }
+    }
+    Ok(selected)
+}
+
+fn ask_continue(goal_document: &GoalDocument, to: &str) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        spanned::bail_here!(
+            "cannot ask which goals continue into {to} since stdin is not a terminal; \
+            name them with `--goal`, or pass `--yes` to continue all of them"
+        );
+    }
+
+    println!(
+        "Does `{}` ({}) continue into {to}? [y/N]",
+        *goal_document.metadata.title,
+        goal_document.path.display()
+    );
+
+    let mut input = String::new();
+    if std::io::stdin().read_line(&mut input)? == 0 {
+        spanned::bail_here!("no answer for `{}`", *goal_document.metadata.title);
+    }
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// The text of `goal_document` as it should appear in the next milestone.
+fn continued_goal_text(goal_document: &GoalDocument) -> Result<String> {
+    let text = std::fs::read_to_string(&goal_document.path)
+        .with_path_context(&goal_document.path, "Failed to read goal")?;
+
+    // Reset the status; everything else in the metadata (including the tracking issue) is kept.
+    let metadata_table = &goal_document.metadata.table;
+    let mut new_table = metadata_table.content.clone();
+    new_table.add_key_value_row("Status", &"Proposed");
+
+    let mut rows = vec![new_table.header];
+    rows.extend(new_table.rows);
+
+    let mut edits = vec![Edit {
+        range: metadata_table.span.bytes.clone(),
+        text: format_table(&rows).trim_end().to_string(),
+    }];
+    edits.extend(migrate::team_asks_edits(goal_document, &text)?);
+
+    Ok(migrate::apply_edits(&text, edits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_project_goals::team;
+
+    const NAMES: &[&str] = &["cargo-plumbing", "parallel-front-end", "rust-for-linux"];
+
+    fn goals(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn never_ask(_: usize) -> Result<bool> {
+        panic!("should not ask")
+    }
+
+    #[test]
+    fn test_select_goals() {
+        // Named goals are continued in the order of the documents, without asking.
+        let named = goals(&["rust-for-linux", "cargo-plumbing"]);
+        assert_eq!(
+            select_goals(NAMES, &named, false, never_ask).unwrap(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            select_goals(NAMES, &named, true, never_ask).unwrap(),
+            vec![0, 2]
+        );
+
+        assert_eq!(
+            select_goals(NAMES, &[], true, never_ask).unwrap(),
+            vec![0, 1, 2]
+        );
+
+        let mut asked = vec![];
+        let selected = select_goals(NAMES, &[], false, |index| {
+            asked.push(index);
+            Ok(index == 1)
+        })
+        .unwrap();
+        assert_eq!(selected, vec![1]);
+        assert_eq!(asked, vec![0, 1, 2]);
+
+        assert!(select_goals(NAMES, &[], false, |_| spanned::bail_here!("no answer")).is_err());
+    }
+
+    #[test]
+    fn test_continued_goal_text() {
+        team::test_support::use_test_checkout();
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("faster-builds.md");
+        std::fs::write(
+            &path,
+            r#"# Faster builds
+
+| Metadata         |                                    |
+|:-----------------|------------------------------------|
+| Point of contact | @alice                             |
+| Status           | Accepted                           |
+| Tracking issue   | [rust-lang/rust-project-goals#123] |
+
+## Summary
+
+Make builds faster.
+
+## Ownership and team asks
+
+| Task             | Owner(s) or team(s)    | Notes |
+|------------------|------------------------|-------|
+| Standard reviews | ![Team][] [compiler][] |       |
+
+## Frequently asked questions
+"#,
+        )
+        .unwrap();
+
+        let goal_document = goal::goal_in_file(&path).unwrap().unwrap();
+        assert_eq!(
+            continued_goal_text(&goal_document).unwrap(),
+            r#"# Faster builds
+
+| Metadata         |                                    |
+| :--              | :--                                |
+| Point of contact | @alice                             |
+| Status           | Proposed                           |
+| Tracking issue   | [rust-lang/rust-project-goals#123] |
+
+## Summary
+
+Make builds faster.
+
+## Team asks
+
+| Team       | Support level | Notes            |
+| :--        | :--   | :--              |
+| [compiler] | Small | Standard reviews |
+
+## Frequently asked questions
+"#
+        );
+    }
+}
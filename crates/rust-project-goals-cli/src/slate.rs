@@ -0,0 +1,104 @@
+//! Generating the blog post that introduces the slate of goals for a milestone.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rust_project_goals::{
+    gh::{
+        issue_id::Repository,
+        issues::{all_checkboxes, list_issues_in_milestone, ExistingGithubIssue},
+    },
+    goal,
+    spanned::{Context as _, Result},
+};
+use rust_project_goals_json::{GithubIssueState, Progress};
+
+use crate::updates::templates::{Slate, SlateGoal, SlateTheme};
+
+/// Renders the post introducing the goals of `milestone` that were not rejected, grouped by
+/// flagship theme, with the summary of each goal and the progress of its tracking issue (if it
+/// has one yet). Writes it to `output_file` or, if `None`, prints it.
+pub fn introduce_slate(
+    repository: &Repository,
+    milestone: &str,
+    output_file: Option<&Path>,
+) -> Result<()> {
+    let goal_documents = goal::goals_in_dir(&Path::new("src").join(milestone))?;
+    let issues = list_issues_in_milestone(repository, milestone)?;
+
+    let tracking_issues: Vec<&ExistingGithubIssue> = issues
+        .iter()
+        .filter(|issue| issue.has_label("C-tracking-issue"))
+        .collect();
+    let progress: BTreeMap<u64, (bool, Progress)> = tracking_issues
+        .iter()
+        .zip(all_checkboxes(tracking_issues.iter().copied()))
+        .map(|(issue, progress)| {
+            (
+                issue.number,
+                (issue.state == GithubIssueState::Closed, progress),
+            )
+        })
+        .collect();
+
+    let mut themes: BTreeMap<String, Vec<SlateGoal>> = BTreeMap::new();
+    let mut other_goals = vec![];
+    for document in goal_documents.iter().filter(|d| d.is_not_not_accepted()) {
+        let (issue_url, (is_closed, progress)) = match &document.metadata.tracking_issue {
+            Some(issue_id) => (
+                issue_id.url(),
+                progress
+                    .get(&issue_id.number)
+                    .cloned()
+                    .unwrap_or((false, Progress::Binary { is_closed: false })),
+            ),
+            None => (
+                format!(
+                    "https://rust-lang.github.io/rust-project-goals/{milestone}/{}",
+                    document.link_path.with_extension("html").display()
+                ),
+                (false, Progress::Binary { is_closed: false }),
+            ),
+        };
+
+        let goal = SlateGoal {
+            title: document.metadata.title.content.clone(),
+            issue_url,
+            summary: document.summary.trim().to_string(),
+            point_of_contact: document.point_of_contact_for_goal_list(),
+            is_closed,
+            progress,
+        };
+        match document.metadata.flagship() {
+            Some(theme) => themes
+                .entry(theme.trim().to_string())
+                .or_default()
+                .push(goal),
+            None => other_goals.push(goal),
+        }
+    }
+
+    for goals in themes.values_mut().chain([&mut other_goals]) {
+        goals.sort_by_cached_key(|goal| goal.title.to_lowercase());
+    }
+    let flagship_goal_count = themes.values().map(Vec::len).sum::<usize>();
+    let slate = Slate {
+        milestone: milestone.to_string(),
+        goal_count: flagship_goal_count + other_goals.len(),
+        flagship_goal_count,
+        flagship_goals_by_theme: themes
+            .into_iter()
+            .map(|(theme_name, goals)| SlateTheme { theme_name, goals })
+            .collect(),
+        other_goals,
+    };
+    let output = slate.render()?;
+
+    match output_file {
+        Some(output_file) => {
+            std::fs::write(output_file, output).with_path_context(output_file, "failed to write")?
+        }
+        None => println!("{output}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use rust_project_goals::gh::{
+    issue_id::Repository,
+    issues::{all_checkboxes, list_issues_in_milestone},
+};
+use rust_project_goals::progress_history::{IssueSnapshot, ProgressHistory, ProgressSnapshot};
+use rust_project_goals::spanned::Result;
+
+/// Record today's progress and comment count of every tracking issue in `milestone`
+/// into the progress history in `directory` (the milestone directory, like `src/2025h2`).
+pub fn snapshot_progress(repository: &Repository, milestone: &str, directory: &Path) -> Result<()> {
+    let issues = list_issues_in_milestone(repository, milestone)?;
+    let tracking_issues: Vec<_> = issues
+        .iter()
+        .filter(|issue| issue.has_label("C-tracking-issue"))
+        .collect();
+
+    let snapshot = ProgressSnapshot {
+        date: chrono::Utc::now().date_naive().to_string(),
+        issues: tracking_issues
+            .iter()
+            .zip(all_checkboxes(tracking_issues.iter().copied()))
+            .map(|(issue, progress)| IssueSnapshot {
+                number: issue.number,
+                title: issue.title.clone(),
+                progress,
+                comments: issue.comments.len(),
+            })
+            .collect(),
+    };
+    let date = snapshot.date.clone();
+    let count = snapshot.issues.len();
+
+    let mut history = ProgressHistory::load(directory)?;
+    history.record(snapshot);
+    let path = history.save(directory)?;
+
+    eprintln!(
+        "recorded the progress of {count} tracking issue(s) on {date} into `{}`",
+        path.display()
+    );
+    Ok(())
+}
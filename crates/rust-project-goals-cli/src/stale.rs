@@ -0,0 +1,338 @@
+//! Finding the goals whose tracking issues have not had an update in a while,
+//! and reminding their owners to post one.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use rust_project_goals::{
+    config::Configuration,
+    gh::{
+        backend::backend,
+        issue_id::{IssueId, Repository},
+        issues::{
+            create_comment, list_issues_in_milestone, ExistingGithubComment, ExistingGithubIssue,
+            STALE_REMINDER_MARKER,
+        },
+    },
+    goal::{self, GoalDocument},
+    spanned::Result,
+};
+use rust_project_goals_json::GithubIssueState;
+
+use crate::updates::templates::StaleReminder;
+
+struct StaleGoal<'i> {
+    issue: &'i ExistingGithubIssue,
+    document: Option<&'i GoalDocument>,
+
+    /// The last comment that counts as an update, if any
+    last_update: Option<&'i ExistingGithubComment>,
+}
+
+/// Reports the open tracking issues in `milestone` whose last update is more than
+/// `after_days` days old (by default, `after_days` from the `[stale]` table of
+/// `rust-project-goals.toml`), grouped by point of contact and by champion team.
+///
+/// With `remind`, also prepares a reminder comment for each of them (skipping those that were
+/// already reminded since their last update), which is only posted with `commit`.
+pub fn stale(
+    repository: &Repository,
+    milestone: &str,
+    after_days: Option<u64>,
+    remind: bool,
+    commit: bool,
+) -> Result<()> {
+    let after_days = after_days.unwrap_or(Configuration::get().stale.after_days);
+    let today = chrono::Utc::now().date_naive();
+    let cutoff = today - chrono::Duration::days(after_days as i64);
+
+    let goal_documents = goal::goals_in_dir(&Path::new("src").join(milestone))?;
+    let issues = list_issues_in_milestone(repository, milestone)?;
+
+    let open_tracking_issues: Vec<&ExistingGithubIssue> = issues
+        .iter()
+        .filter(|issue| {
+            issue.has_label("C-tracking-issue") && issue.state == GithubIssueState::Open
+        })
+        .collect();
+
+    let stale_goals: Vec<StaleGoal> = select_stale(&open_tracking_issues, cutoff)
+        .into_iter()
+        .map(|(issue, last_update)| {
+            let document = goal_documents.iter().find(|document| {
+                document
+                    .metadata
+                    .tracking_issue
+                    .as_ref()
+                    .is_some_and(|id| id.number == issue.number)
+            });
+            StaleGoal {
+                issue,
+                document,
+                last_update,
+            }
+        })
+        .collect();
+
+    println!(
+        "{} of {} open tracking issue(s) in {milestone} have had no update in the last {after_days} days.",
+        stale_goals.len(),
+        open_tracking_issues.len(),
+    );
+
+    let mut by_point_of_contact: BTreeMap<String, Vec<&StaleGoal>> = BTreeMap::new();
+    let mut by_champion_team: BTreeMap<String, Vec<&StaleGoal>> = BTreeMap::new();
+    for goal in &stale_goals {
+        let Some(document) = goal.document else {
+            by_point_of_contact
+                .entry("(no goal document)".to_string())
+                .or_default()
+                .push(goal);
+            continue;
+        };
+
+        by_point_of_contact
+            .entry(document.metadata.pocs.clone())
+            .or_default()
+            .push(goal);
+        if document.metadata.champions.is_empty() {
+            by_champion_team
+                .entry("(no champion)".to_string())
+                .or_default()
+                .push(goal);
+        }
+        for team in document.metadata.champions.keys() {
+            by_champion_team
+                .entry(team.to_string())
+                .or_default()
+                .push(goal);
+        }
+    }
+
+    for (heading, groups) in [
+        ("By point of contact", &by_point_of_contact),
+        ("By champion team", &by_champion_team),
+    ] {
+        if groups.is_empty() {
+            continue;
+        }
+        println!("\n## {heading}");
+        for (group, goals) in groups {
+            println!("\n### {group}\n");
+            for goal in goals {
+                println!("* {}", goal.describe(repository, today));
+            }
+        }
+    }
+
+    if !remind {
+        return Ok(());
+    }
+
+    println!();
+    if !commit {
+        println!("Dry run mode - pass `--commit` to post the reminders");
+    }
+    for goal in &stale_goals {
+        let number = goal.issue.number;
+        if goal.was_reminded() {
+            println!("Skipping #{number}: already reminded since its last update");
+            continue;
+        }
+
+        let body = goal.reminder(after_days)?;
+        if commit {
+            create_comment(repository, number, &body)?;
+            println!("Reminded #{number}");
+            std::thread::sleep(backend().pause_between_writes());
+        } else {
+            println!("Would post on #{number}:\n\n{body}\n");
+        }
+    }
+
+    Ok(())
+}
+
+/// The issues among `issues` whose last update is before `cutoff`, along with that update
+/// (if they had one), starting with the issues that have gone without an update the longest.
+/// Hidden and automated comments do not count as updates.
+fn select_stale<'i>(
+    issues: &[&'i ExistingGithubIssue],
+    cutoff: NaiveDate,
+) -> Vec<(&'i ExistingGithubIssue, Option<&'i ExistingGithubComment>)> {
+    let mut stale = vec![];
+    for &issue in issues {
+        let last_update = issue
+            .comments
+            .iter()
+            .filter(|c| !c.should_hide_from_reports())
+            .max_by(|a, b| a.created_at.cmp(&b.created_at));
+        if last_update.is_some_and(|c| c.created_at_date() >= cutoff) {
+            continue;
+        }
+        stale.push((issue, last_update));
+    }
+    stale.sort_by_key(|(_, last_update)| last_update.map(|c| c.created_at.clone()));
+    stale
+}
+
+impl StaleGoal<'_> {
+    fn describe(&self, repository: &Repository, today: NaiveDate) -> String {
+        let url = IssueId::new(repository.clone(), self.issue.number).url();
+        let last_update = match self.last_update {
+            Some(comment) => {
+                let date = comment.created_at_date();
+                format!(
+                    "last update {date} ({} days ago)",
+                    (today - date).num_days()
+                )
+            }
+            None => "no updates yet".to_string(),
+        };
+        format!("[{}]({url}): {last_update}", self.issue.title)
+    }
+
+    /// True if a reminder was posted after the last update.
+    fn was_reminded(&self) -> bool {
+        self.issue.comments.iter().any(|c| {
+            c.body.trim().starts_with(STALE_REMINDER_MARKER)
+                && match self.last_update {
+                    Some(update) => c.created_at > update.created_at,
+                    None => true,
+                }
+        })
+    }
+
+    fn reminder(&self, after_days: u64) -> Result<String> {
+        // Remind the assignees or, if there are none, the point of contact.
+        let mentions = if !self.issue.assignees.is_empty() {
+            self.issue
+                .assignees
+                .iter()
+                .map(|assignee| format!("@{assignee}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            self.document
+                .map(|document| document.metadata.pocs.clone())
+                .unwrap_or_else(|| "there".to_string())
+        };
+
+        let text = StaleReminder {
+            title: self.issue.title.clone(),
+            mentions,
+            last_update: self
+                .last_update
+                .map(|comment| comment.created_at_date().to_string()),
+            after_days,
+        }
+        .render()?;
+        Ok(format!("{STALE_REMINDER_MARKER}\n{text}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(body: &str, created_at: &str, hidden: bool) -> ExistingGithubComment {
+        serde_json::from_value(serde_json::json!({
+            "author": "@alice",
+            "body": body,
+            "created_at": created_at,
+            "url": "https://github.com/rust-lang/rust-project-goals/issues/1#issuecomment-1",
+            "hidden": hidden,
+        }))
+        .unwrap()
+    }
+
+    fn update(created_at: &str) -> ExistingGithubComment {
+        comment("We landed the parser.", created_at, false)
+    }
+
+    fn reminder(created_at: &str) -> ExistingGithubComment {
+        comment(
+            &format!("{STALE_REMINDER_MARKER}\nPlease post an update."),
+            created_at,
+            false,
+        )
+    }
+
+    fn issue(number: u64, comments: Vec<ExistingGithubComment>) -> ExistingGithubIssue {
+        ExistingGithubIssue {
+            number,
+            title: format!("Goal {number}"),
+            assignees: Default::default(),
+            comments,
+            body: String::new(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_select_stale() {
+        let cutoff = NaiveDate::from_ymd_opt(2025, 10, 1).unwrap();
+        let issues = [
+            // Updated on the cutoff date, so not stale.
+            issue(1, vec![update("2025-10-01T00:00:00Z")]),
+            issue(2, vec![update("2025-09-30T23:59:59Z")]),
+            issue(3, vec![]),
+            // Hidden and automated comments are not updates.
+            issue(
+                4,
+                vec![
+                    update("2025-09-01T12:00:00Z"),
+                    comment("Off-topic.", "2025-10-02T12:00:00Z", true),
+                    reminder("2025-10-03T12:00:00Z"),
+                ],
+            ),
+        ];
+        let issues: Vec<&ExistingGithubIssue> = issues.iter().collect();
+
+        let stale: Vec<(u64, Option<&str>)> = select_stale(&issues, cutoff)
+            .into_iter()
+            .map(|(issue, last_update)| (issue.number, last_update.map(|c| c.created_at.as_str())))
+            .collect();
+        assert_eq!(
+            stale,
+            vec![
+                (3, None),
+                (4, Some("2025-09-01T12:00:00Z")),
+                (2, Some("2025-09-30T23:59:59Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_was_reminded() {
+        let was_reminded = |comments: Vec<ExistingGithubComment>| {
+            let issue = issue(1, comments);
+            let last_update = issue
+                .comments
+                .iter()
+                .find(|c| !c.should_hide_from_reports());
+            StaleGoal {
+                issue: &issue,
+                document: None,
+                last_update,
+            }
+            .was_reminded()
+        };
+
+        assert!(!was_reminded(vec![update("2025-09-01T12:00:00Z")]));
+        assert!(was_reminded(vec![
+            update("2025-09-01T12:00:00Z"),
+            reminder("2025-09-20T12:00:00Z"),
+        ]));
+        // An update since the reminder makes the goal due for another one.
+        assert!(!was_reminded(vec![
+            reminder("2025-08-20T12:00:00Z"),
+            update("2025-09-01T12:00:00Z"),
+        ]));
+        assert!(was_reminded(vec![reminder("2025-08-20T12:00:00Z")]));
+    }
+}
@@ -4,14 +4,15 @@ use rust_project_goals::re::{HELP_WANTED, TLDR};
 use rust_project_goals::spanned::{Result, Span, Spanned};
 use rust_project_goals::util::{comma, MILESTONE_REGEX};
 use rust_project_goals::{goal, markwaydown, spanned, team};
-use rust_project_goals_json::GithubIssueState;
+use rust_project_goals_json::{GithubIssueState, Progress};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-mod templates;
+pub mod templates;
 use rust_project_goals::gh::issues::ExistingGithubIssue;
 use rust_project_goals::gh::{
     issue_id::{IssueId, Repository},
-    issues::{checkboxes, ExistingGithubComment},
+    issues::{all_checkboxes, ExistingGithubComment},
 };
 use templates::{HelpWanted, UpdatesGoal};
 
@@ -27,6 +28,44 @@ pub enum Order {
     NewestFirst,
 }
 
+/// The formats in which updates can be rendered. Each one but `Json` has its own set of
+/// templates (see [`UpdatesFormat::templates_subdir`]).
+#[derive(clap::ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UpdatesFormat {
+    /// Markdown for the Rust blog.
+    #[default]
+    Blog,
+    /// A self-contained HTML page.
+    Html,
+    /// A plain-text digest, suitable for sending by email.
+    Email,
+    /// The goals and their updates as JSON.
+    Json,
+}
+
+impl UpdatesFormat {
+    /// Subdirectory of the templates directory whose templates replace the default ones
+    /// (those in the templates directory itself, which render the blog post).
+    pub fn templates_subdir(self) -> Option<&'static str> {
+        match self {
+            UpdatesFormat::Blog | UpdatesFormat::Json => None,
+            UpdatesFormat::Html => Some("html"),
+            UpdatesFormat::Email => Some("email"),
+        }
+    }
+}
+
+/// Which part of the updates on a goal [`render_goal`] renders.
+#[derive(Copy, Clone, Debug)]
+pub enum GoalView {
+    /// The card of the goal, as in the monthly updates (`goal_card.hbs`):
+    /// its progress, TL;DR, help wanted, and updates.
+    Card,
+
+    /// Only the updates on the goal (`goal_comments.hbs`).
+    Comments,
+}
+
 /// Library function that renders updates as a string without side effects.
 /// This is suitable for use from the mdbook preprocessor.
 pub fn render_updates(
@@ -38,7 +77,73 @@ pub fn render_updates(
     with_champion_from: Option<&str>,
     use_progress_bar: bool,
     comment_order: Order,
+    format: UpdatesFormat,
+) -> Result<String> {
+    let updates = prepare_updates(
+        cached_issues,
+        repository,
+        milestone,
+        start_date,
+        end_date,
+        with_champion_from,
+        use_progress_bar,
+        comment_order,
+    )?;
+
+    // Render the output using handlebars (or serde, for JSON) and return it
+    updates.render(format)
+}
+
+/// Renders the goal whose tracking issue is `issue_number`, with the updates posted on it
+/// between `start_date` and `end_date`, as it appears in the monthly updates.
+/// Like [`render_updates`], this is suitable for use from the mdbook preprocessor.
+pub fn render_goal(
+    cached_issues: &[ExistingGithubIssue],
+    repository: &Repository,
+    milestone: &str,
+    issue_number: u64,
+    start_date: &Option<NaiveDate>,
+    end_date: &Option<NaiveDate>,
+    view: GoalView,
 ) -> Result<String> {
+    let issues: Vec<ExistingGithubIssue> = cached_issues
+        .iter()
+        .filter(|issue| issue.number == issue_number)
+        .cloned()
+        .collect();
+    if issues.is_empty() {
+        spanned::bail_here!("no issue #{issue_number} in milestone {milestone}");
+    }
+
+    let updates = prepare_updates(
+        &issues,
+        repository,
+        milestone,
+        start_date,
+        end_date,
+        None,
+        false,
+        Order::OldestFirst,
+    )?;
+    let Some(goal) = updates.into_goals().next() else {
+        spanned::bail_here!("no goal is tracked by issue #{issue_number}");
+    };
+    match view {
+        GoalView::Card => goal.render_card(),
+        GoalView::Comments => goal.render_comments(),
+    }
+}
+
+fn prepare_updates(
+    cached_issues: &[ExistingGithubIssue],
+    repository: &Repository,
+    milestone: &str,
+    start_date: &Option<NaiveDate>,
+    end_date: &Option<NaiveDate>,
+    with_champion_from: Option<&str>,
+    use_progress_bar: bool,
+    comment_order: Order,
+) -> Result<templates::Updates> {
     let milestone_re = Regex::new(MILESTONE_REGEX).unwrap();
     if !milestone_re.is_match(milestone) {
         spanned::bail_here!(
@@ -160,6 +265,13 @@ pub fn render_updates(
         end_date,
     };
 
+    // Computing the progress may fetch many other issues, so do it for all goals at once.
+    let progress: BTreeMap<u64, Progress> = filtered_issues
+        .iter()
+        .map(|issue| issue.number)
+        .zip(all_checkboxes(&filtered_issues))
+        .collect();
+
     if use_progress_bar {
         progress_bar::init_progress_bar(filtered_issues.len());
         progress_bar::set_progress_bar_action(
@@ -172,6 +284,7 @@ pub fn render_updates(
     let flagship_goals = prepare_goals(
         repository,
         &filtered_issues,
+        &progress,
         &filter,
         true,
         use_progress_bar,
@@ -184,6 +297,7 @@ pub fn render_updates(
     let other_goals = prepare_goals(
         repository,
         &filtered_issues,
+        &progress,
         &filter,
         false,
         use_progress_bar,
@@ -199,13 +313,13 @@ pub fn render_updates(
         progress_bar::finalize_progress_bar();
     }
 
-    // Render the output using handlebars and return it
-    updates.render()
+    Ok(updates)
 }
 
 fn prepare_goals(
     repository: &Repository,
     issues: &[ExistingGithubIssue],
+    progress: &BTreeMap<u64, Progress>,
     filter: &Filter<'_>,
     flagship: bool,
     use_progress_bar: bool,
@@ -238,7 +352,7 @@ fn prepare_goals(
             );
         }
 
-        let progress = checkboxes(&issue);
+        let progress = progress[&issue.number].clone();
 
         let mut comments = issue.comments.clone();
         comments.sort_by_key(|c| c.created_at.clone());
@@ -258,6 +372,17 @@ fn prepare_goals(
 
         let (has_help_wanted, help_wanted) = help_wanted(&issue_id, &tldr, &comments)?;
 
+        // Without a TL;DR from the owners, summarize their latest update.
+        let tldr_is_generated = tldr.is_none();
+        let tldr = tldr.or_else(|| {
+            let latest = match comment_order {
+                Order::OldestFirst => comments.last(),
+                Order::NewestFirst => comments.first(),
+            };
+            latest.and_then(|comment| fallback_tldr(&comment.body))
+        });
+        let tldr_is_generated = tldr_is_generated && tldr.is_some();
+
         let why_this_goal = why_this_goal(&issue_id, issue)?;
 
         let details_summary = match comments.len() {
@@ -278,6 +403,7 @@ fn prepare_goals(
             details_summary,
             comments,
             tldr,
+            tldr_is_generated,
             why_this_goal,
             needs_separator: true, // updated after sorting
             theme: issue_themes.get(&issue.number).cloned(),
@@ -315,16 +441,58 @@ fn prepare_goals(
 fn tldr(_issue_id: &IssueId, comments: &mut Vec<ExistingGithubComment>) -> Result<Option<String>> {
     // `comments` are sorted by creation date in an ascending order, so we look for the most recent
     // TL;DR comment from the end.
-    let Some(index) = comments.iter().rposition(|c| c.body.starts_with(TLDR)) else {
+    let Some(index) = comments.iter().rposition(|c| tldr_text(c).is_some()) else {
         return Ok(None);
     };
 
     let comment = comments.remove(index);
-    Ok(Some(comment.body[TLDR.len()..].trim().to_string()))
+    Ok(tldr_text(&comment).map(str::to_string))
+}
+
+/// The text of `comment` after `TL;DR:`, if it is a TL;DR comment.
+pub(crate) fn tldr_text(comment: &ExistingGithubComment) -> Option<&str> {
+    comment.body.strip_prefix(TLDR).map(str::trim)
+}
+
+/// A summary of an update without a TL;DR: its first paragraph or, if it starts with a
+/// list, the top-level items of that list (without the items nested in them).
+/// Headings and HTML comments before it are skipped.
+fn fallback_tldr(body: &str) -> Option<String> {
+    let mut lines = body
+        .lines()
+        .map(str::trim_end)
+        .skip_while(|line| {
+            let line = line.trim_start();
+            line.is_empty() || line.starts_with('#') || line.starts_with("<!--")
+        })
+        .peekable();
+
+    let is_item = |line: &str| {
+        let marker = line.split_whitespace().next().unwrap_or("");
+        line.starts_with(marker)
+            && (["*", "-", "+"].contains(&marker)
+                || marker
+                    .strip_suffix('.')
+                    .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())))
+    };
+
+    let first = *lines.peek()?;
+    let summary: Vec<&str> = if is_item(first) {
+        // The list ends at the first line that is neither an item nor indented
+        // (items may be separated by blank lines).
+        lines
+            .take_while(|line| line.is_empty() || line.starts_with(' ') || is_item(line))
+            .filter(|line| !line.starts_with(' ') && !line.is_empty())
+            .collect()
+    } else {
+        lines.take_while(|line| !line.trim().is_empty()).collect()
+    };
+
+    Some(summary.join("\n"))
 }
 
 /// Search for comments that talk about help being wanted and extract that
-fn help_wanted(
+pub(crate) fn help_wanted(
     _issue_id: &IssueId,
     tldr: &Option<String>,
     comments: &[ExistingGithubComment],
@@ -400,8 +568,32 @@ impl Filter<'_> {
     }
 }
 
-fn default_start_date() -> NaiveDate {
+pub(crate) fn default_start_date() -> NaiveDate {
     let date = chrono::Utc::now().date_naive();
     let start_of_month = NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap();
     start_of_month - chrono::Duration::days(7)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_tldr() {
+        assert_eq!(
+            fallback_tldr("## Update\n\nWe landed the parser.\nReviews are next.\n\nMore details.")
+                .as_deref(),
+            Some("We landed the parser.\nReviews are next.")
+        );
+        assert_eq!(
+            fallback_tldr("* Landed #123\n  * with a follow-up\n\n* Opened #124\n\nThanks!")
+                .as_deref(),
+            Some("* Landed #123\n* Opened #124")
+        );
+        assert_eq!(
+            fallback_tldr("1. First\n2. Second\nNot an item").as_deref(),
+            Some("1. First\n2. Second")
+        );
+        assert_eq!(fallback_tldr("<!-- nothing -->\n\n"), None);
+    }
+}
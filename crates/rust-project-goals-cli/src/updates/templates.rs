@@ -1,53 +1,113 @@
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use handlebars::{
     Context, DirectorySourceOptions, Handlebars, Helper, HelperDef, HelperResult, Output,
     RenderContext, RenderErrorReason,
 };
-use rust_project_goals::config::GoalsConfig;
+use rust_project_goals::config::{Configuration, GoalsConfig};
 use rust_project_goals::gh::issues::ExistingGithubComment;
 use rust_project_goals::markdown_processor::{MarkdownProcessor, MarkdownProcessorState};
 use serde::Serialize;
 
-use rust_project_goals::spanned::Result;
+use rust_project_goals::spanned::{self, Error, Result};
 use rust_project_goals_json::Progress;
 
+use super::UpdatesFormat;
+
 pub struct Templates<'h> {
     reg: Handlebars<'h>,
 }
 
+/// Embeds each named file of `templates/` (without its `.hbs` extension) in the binary.
+macro_rules! builtin_templates {
+    ($($name:literal),* $(,)?) => {
+        &[$(($name, include_str!(concat!("../../../../templates/", $name, ".hbs")))),*]
+    };
+}
+
+/// The templates built into the binary, named after their file in `templates/`
+/// (e.g., `html/updates` for `templates/html/updates.hbs`).
+const BUILTIN_TEMPLATES: &[(&str, &str)] = builtin_templates![
+    "feed",
+    "goal_card",
+    "goal_comments",
+    "introduce_goal",
+    "introduce_slate",
+    "progress",
+    "progress_sources",
+    "request_updates",
+    "stale_reminder",
+    "updates",
+    "email/goal_card",
+    "email/progress",
+    "email/updates",
+    "html/updates",
+];
+
+static TEMPLATES_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Select a directory of templates to layer over the built-in ones.
+/// This must be called before any template is rendered.
+pub fn set_templates_dir(dir_path: PathBuf) -> Result<()> {
+    TEMPLATES_DIR
+        .set(dir_path)
+        .map_err(|_| Error::str("the templates directory was already selected".to_string()))
+}
+
+/// The directory of templates layered over the built-in ones: the one given to
+/// [`set_templates_dir`] or, if none, the `directory` in the `[templates]` table
+/// of the `rust-project-goals.toml` in the current directory (if there is one).
+fn templates_dir() -> Result<Option<&'static Path>> {
+    if let Some(dir_path) = TEMPLATES_DIR.get() {
+        return Ok(Some(dir_path.as_path()));
+    }
+    Ok(Configuration::from_working_directory()?
+        .and_then(|config| config.templates.directory.as_deref()))
+}
+
 impl<'h> Templates<'h> {
+    /// The built-in templates, with those of the selected templates directory (if any)
+    /// layered over them. Markdown is linkified with the configuration in `book.toml`,
+    /// if there is one in the current directory.
     pub fn new() -> Result<Self> {
-        let templates = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../templates");
-
-        // Load config from book.toml using clean approach
-        let book_toml_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../book.toml");
+        let book_toml_path = Path::new("book.toml");
         let goals_config = if book_toml_path.exists() {
-            GoalsConfig::from_book_toml(book_toml_path).map_err(|e| {
-                rust_project_goals::spanned::Error::str(format!(
-                    "Failed to load goals config: {}",
-                    e
-                ))
-            })?
+            GoalsConfig::from_book_toml(book_toml_path)
+                .map_err(|e| Error::str(format!("Failed to load goals config: {}", e)))?
         } else {
             GoalsConfig::default()
         };
 
-        Self::from_templates_dir(&templates, goals_config)
+        match templates_dir()? {
+            Some(dir_path) => Self::from_templates_dir(dir_path, goals_config),
+            None => Self::builtin(goals_config),
+        }
     }
 
+    /// The built-in templates, with those in `dir_path` layered over them.
     pub fn from_templates_dir(
         dir_path: impl AsRef<Path>,
         goals_config: GoalsConfig,
     ) -> Result<Self> {
         let dir_path = dir_path.as_ref();
+        if !dir_path.is_dir() {
+            spanned::bail_here!(
+                "the templates directory `{}` does not exist",
+                dir_path.display()
+            );
+        }
+        Self::builtin(goals_config)?.with_overrides(dir_path)
+    }
+
+    fn builtin(goals_config: GoalsConfig) -> Result<Self> {
         let mut reg = Handlebars::new();
 
         reg.set_strict_mode(true);
 
-        reg.register_templates_directory(dir_path, DirectorySourceOptions::default())?;
-        assert!(reg.get_template("updates").is_some());
+        for (name, text) in BUILTIN_TEMPLATES {
+            reg.register_template_string(name, text)?;
+        }
 
         // Create the shared markdown processor
         let markdown_processor = MarkdownProcessor::new(goals_config);
@@ -60,6 +120,36 @@ impl<'h> Templates<'h> {
 
         Ok(Templates { reg })
     }
+
+    /// The templates for rendering updates in `format`: those in its subdirectory
+    /// (e.g., `html`) replace the ones with the same name, first among the built-in
+    /// templates and then in the selected templates directory.
+    pub fn for_format(format: UpdatesFormat) -> Result<Self> {
+        let mut templates = Self::new()?;
+        let Some(subdir) = format.templates_subdir() else {
+            return Ok(templates);
+        };
+
+        let prefix = format!("{subdir}/");
+        for (name, text) in BUILTIN_TEMPLATES {
+            if let Some(name) = name.strip_prefix(&prefix) {
+                templates.reg.register_template_string(name, text)?;
+            }
+        }
+
+        match templates_dir()?.map(|dir_path| dir_path.join(subdir)) {
+            Some(dir_path) if dir_path.is_dir() => templates.with_overrides(dir_path),
+            _ => Ok(templates),
+        }
+    }
+
+    /// Registers the templates in `dir_path` over the ones already loaded,
+    /// replacing those with the same name.
+    fn with_overrides(mut self, dir_path: impl AsRef<Path>) -> Result<Self> {
+        self.reg
+            .register_templates_directory(dir_path.as_ref(), DirectorySourceOptions::default())?;
+        Ok(self)
+    }
 }
 
 /// Custom handlebars helper that processes markdown with linking
@@ -104,7 +194,9 @@ impl HelperDef for MarkdownToHtmlHelper {
 
 handlebars::handlebars_helper!(is_complete: |p: Progress| match p {
     Progress::Binary { is_closed } => is_closed,
-    Progress::Tracked { completed, total } => completed == total,
+    Progress::Tracked { completed, total } | Progress::Weighted { completed, total, .. } => {
+        completed == total
+    }
     Progress::Error { .. } => false,
 });
 
@@ -155,10 +247,21 @@ impl Updates {
             other_goals,
         }
     }
-    pub fn render(self) -> Result<String> {
-        let templates = Templates::new()?;
+    pub fn render(self, format: UpdatesFormat) -> Result<String> {
+        if format == UpdatesFormat::Json {
+            return Ok(serde_json::to_string_pretty(&self)?);
+        }
+        let templates = Templates::for_format(format)?;
         Ok(templates.reg.render("updates", &self)?)
     }
+
+    /// All the goals, flagship goals first.
+    pub fn into_goals(self) -> impl Iterator<Item = UpdatesGoal> {
+        self.flagship_goals_by_theme
+            .into_iter()
+            .flat_map(|theme| theme.goals)
+            .chain(self.other_goals)
+    }
 }
 
 /// Part of the parameters expected by the `updates.md` template.
@@ -201,6 +304,9 @@ pub struct UpdatesGoal {
     /// TL;DR comment (if any, empty string if none)
     pub tldr: Option<String>,
 
+    /// True if the owners posted no TL;DR, so `tldr` was generated from their latest update.
+    pub tldr_is_generated: bool,
+
     /// Contents of a "Why this goal?" section in the tracking issue (empty string if not present)
     pub why_this_goal: String,
 
@@ -221,6 +327,146 @@ pub struct UpdatesGoal {
     pub task_owners: String,
 }
 
+impl UpdatesGoal {
+    /// Renders the card of this goal with the `goal_card.hbs` template.
+    pub fn render_card(&self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("goal_card", self)?)
+    }
+
+    /// Renders the updates on this goal with the `goal_comments.hbs` template.
+    pub fn render_comments(&self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("goal_comments", self)?)
+    }
+}
+
+/// The parameters expected by the `introduce_slate.hbs` template.
+#[derive(Serialize, Debug)]
+pub struct Slate {
+    pub milestone: String,
+    pub flagship_goals_by_theme: Vec<SlateTheme>,
+    pub other_goals: Vec<SlateGoal>,
+    pub goal_count: usize,
+    pub flagship_goal_count: usize,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SlateTheme {
+    pub theme_name: String,
+    pub goals: Vec<SlateGoal>,
+}
+
+/// The parameters expected by the `introduce_goal.hbs` template.
+#[derive(Serialize, Debug)]
+pub struct SlateGoal {
+    /// Title of the goal
+    pub title: String,
+
+    /// URL of the tracking issue or, if there is none yet, of the goal document in the book
+    pub issue_url: String,
+
+    /// Markdown from the summary section of the goal document
+    pub summary: String,
+
+    /// Point of contact for the goal
+    pub point_of_contact: String,
+
+    /// True if the tracking issue is closed.
+    pub is_closed: bool,
+
+    /// Progress towards the goal (`Binary` if there is no tracking issue yet)
+    pub progress: Progress,
+}
+
+impl Slate {
+    pub fn render(self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("introduce_slate", &self)?)
+    }
+}
+
+/// The parameters expected by the `stale_reminder.hbs` template.
+#[derive(Serialize, Debug)]
+pub struct StaleReminder {
+    /// Title of the tracking issue
+    pub title: String,
+
+    /// The people to remind, like `@alice, @bob`
+    pub mentions: String,
+
+    /// Date of the last update, if there was one
+    pub last_update: Option<String>,
+
+    /// How many days without an update make a goal stale
+    pub after_days: u64,
+}
+
+impl StaleReminder {
+    pub fn render(self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("stale_reminder", &self)?)
+    }
+}
+
+/// The parameters expected by the `request_updates.hbs` template.
+#[derive(Serialize, Debug)]
+pub struct UpdateRequest {
+    /// Title of the tracking issue
+    pub title: String,
+
+    /// The assignees of the tracking issue, like `@alice, @bob`
+    pub mentions: String,
+
+    /// Date since which the goal has had no update
+    pub since: String,
+}
+
+impl UpdateRequest {
+    pub fn render(self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("request_updates", &self)?)
+    }
+}
+
+/// The parameters expected by the `feed.hbs` template, an Atom feed.
+/// Dates are RFC 3339 timestamps like `2025-10-01T12:00:00Z`.
+#[derive(Serialize, Debug)]
+pub struct Feed {
+    pub id: String,
+    pub title: String,
+    pub link: String,
+    pub updated: String,
+    pub entries: Vec<FeedEntry>,
+}
+
+/// One status update in a [`Feed`].
+#[derive(Serialize, Debug, Clone)]
+pub struct FeedEntry {
+    /// URL of the comment, which is also the id of the entry
+    pub url: String,
+    pub title: String,
+
+    /// Just github username, no `@`
+    pub author: String,
+    pub published: String,
+
+    /// The TL;DR of the update and what help is wanted, if any
+    pub summary: Option<String>,
+
+    pub has_help_wanted: bool,
+
+    /// The comment rendered to HTML
+    pub content: String,
+}
+
+impl Feed {
+    pub fn render(self) -> Result<String> {
+        let templates = Templates::new()?;
+        Ok(templates.reg.render("feed", &self)?)
+    }
+}
+
 #[derive(Serialize, Debug, Clone)]
 pub struct HelpWanted {
     pub text: String,
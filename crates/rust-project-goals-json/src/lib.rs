@@ -6,13 +6,26 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Current version of this API.
+///
+/// * Version 1 is the original format (files without a `version` are version 1).
+/// * Version 2 added [`Progress::Weighted`].
+pub const VERSION: u32 = 2;
+
 #[derive(Serialize, Deserialize)]
 pub struct TrackingIssues {
+    /// Version of this API that the data was written with, see [`VERSION`]
+    #[serde(default = "version_1")]
+    pub version: u32,
     pub repository: String,
     pub milestone: String,
     pub issues: Vec<TrackingIssue>,
 }
 
+fn version_1() -> u32 {
+    1
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct TrackingIssue {
     /// Issue number on the repository
@@ -67,11 +80,68 @@ pub enum Progress {
         total: u32,
     },
 
+    /// We found more than one source of progress (or sources that were given weights),
+    /// so we keep the progress of each one. Added in version 2.
+    Weighted {
+        /// Share of the work that is done, from 0 to 1, taking the weights into account.
+        fraction: f64,
+
+        /// Number of completed items across all sources, ignoring the weights.
+        completed: u32,
+
+        /// Number of items across all sources, ignoring the weights.
+        total: u32,
+
+        sources: Vec<ProgressSource>,
+    },
+
     Error {
         message: String,
     },
 }
 
+/// One source of progress of a tracking issue.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProgressSource {
+    /// Short description of the source for display,
+    /// like the search query or the title of the issue
+    pub label: String,
+
+    pub kind: ProgressSourceKind,
+
+    pub completed: u32,
+
+    pub total: u32,
+
+    /// Weight given to the source in the tracking issue, if any.
+    /// Sources without a weight count in proportion to their number of items.
+    pub weight: Option<f64>,
+
+    /// For `See also` issues whose own progress has more than one source, those sources
+    #[serde(default)]
+    pub sources: Vec<ProgressSource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ProgressSourceKind {
+    /// The checkboxes in the body of the issue
+    Checkboxes,
+
+    /// A `Tracked issues` search
+    TrackedIssues { repository: String, query: String },
+
+    /// A `Tracked PRs` search. Merged PRs are completed; PRs closed without
+    /// being merged are not counted, other than in `closed_unmerged`.
+    TrackedPrs {
+        repository: String,
+        query: String,
+        closed_unmerged: u32,
+    },
+
+    /// An issue listed under `See also`
+    SeeAlso { repository: String, number: u64 },
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct TrackingIssueUpdate {
     pub author: String,
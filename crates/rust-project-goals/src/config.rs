@@ -1,17 +1,131 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
 
 use anyhow::Context;
 use indexmap::IndexMap;
 use regex::Regex;
 use serde::Deserialize;
-use spanned::{Context as _, Result};
+use spanned::{Context as _, Error, Result};
+
+use crate::goal::SupportLevel;
+use crate::lint::LintLevel;
+
+/// Name of the configuration file, at the root of the repository.
+pub const CONFIG_FILE: &str = "rust-project-goals.toml";
 
 #[derive(Deserialize)]
 pub struct Configuration {
     /// Defines the valid "asks" of teams. The key is the ask, the value is an extended description.
     /// IndexMap is used to preserve the ordering as defined in the TOML file.
     pub team_asks: IndexMap<String, TeamAskDetails>,
+
+    /// Levels of the lints reported by `cargo rpg check`, overriding their defaults.
+    /// The key is the id of the lint (see `lint::LINTS`).
+    #[serde(default)]
+    pub lints: BTreeMap<String, LintLevel>,
+
+    /// Where the snapshot of the Rust team data is kept (see `team::TeamDataOptions`).
+    #[serde(default)]
+    pub team_data: TeamDataConfig,
+
+    /// Where github data is cached between runs (see `gh::backend::Cache`).
+    #[serde(default)]
+    pub github_cache: GithubCacheConfig,
+
+    /// When goals count as stale (see `cargo rpg stale`).
+    #[serde(default)]
+    pub stale: StaleConfig,
+
+    /// Templates to use instead of the built-in ones (see `--templates`).
+    #[serde(default)]
+    pub templates: TemplatesConfig,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct TeamDataConfig {
+    /// Directory for the snapshot, relative to the directory the command is run from.
+    pub snapshot_dir: PathBuf,
+
+    /// How many hours a snapshot is used before the data is downloaded again.
+    pub ttl_hours: u64,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct GithubCacheConfig {
+    /// Directory for the cache, relative to the directory the command is run from.
+    pub directory: PathBuf,
+
+    /// For how many minutes cached data is used without checking whether it changed.
+    pub ttl_minutes: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct StaleConfig {
+    /// A goal is stale if its tracking issue has had no update for this many days.
+    pub after_days: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct TemplatesConfig {
+    /// Directory of handlebars templates layered over the built-in ones,
+    /// relative to the directory the command is run from.
+    pub directory: Option<PathBuf>,
+}
+
+impl Default for StaleConfig {
+    fn default() -> Self {
+        StaleConfig { after_days: 30 }
+    }
+}
+
+impl GithubCacheConfig {
+    /// The `[github_cache]` table of the `rust-project-goals.toml` in the current directory,
+    /// or the defaults if there is no such file.
+    pub fn from_working_directory() -> Result<GithubCacheConfig> {
+        Ok(match Configuration::from_working_directory()? {
+            Some(config) => config.github_cache.clone(),
+            None => GithubCacheConfig::default(),
+        })
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_minutes * 60)
+    }
+}
+
+impl Default for GithubCacheConfig {
+    fn default() -> Self {
+        GithubCacheConfig {
+            directory: PathBuf::from("target/github-cache"),
+            ttl_minutes: 5,
+        }
+    }
+}
+
+impl TeamDataConfig {
+    /// The `[team_data]` table of the `rust-project-goals.toml` in the current directory,
+    /// or the defaults if there is no such file.
+    pub fn from_working_directory() -> Result<TeamDataConfig> {
+        Ok(match Configuration::from_working_directory()? {
+            Some(config) => config.team_data.clone(),
+            None => TeamDataConfig::default(),
+        })
+    }
+}
+
+impl Default for TeamDataConfig {
+    fn default() -> Self {
+        TeamDataConfig {
+            snapshot_dir: PathBuf::from("target/team-data"),
+            ttl_hours: 24,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -25,6 +139,11 @@ pub struct TeamAskDetails {
     /// If true, do not include in the RFC tables.
     #[serde(default)]
     pub elide: bool,
+
+    /// The 2026 support level we suggest when migrating a goal with this ask
+    /// from the old format (see `cargo rpg migrate`). Defaults to `Small`.
+    #[serde(default)]
+    pub support_level: Option<SupportLevel>,
 }
 
 impl Configuration {
@@ -42,6 +161,31 @@ impl Configuration {
             .with_path_context(&toml_file, "loading configuration")?;
         Ok(toml::from_str(&toml_string)?)
     }
+
+    /// The configuration in the `rust-project-goals.toml` of the directory the command is
+    /// run from, or `None` if there is none. Unlike [`Configuration::get`], this works
+    /// outside of a checkout of this repository; settings that are only needed at runtime
+    /// (like the cache directory) should be read from here and fall back to their defaults.
+    pub fn from_working_directory() -> Result<Option<&'static Configuration>> {
+        static CONFIG: OnceLock<Option<Configuration>> = OnceLock::new();
+        if let Some(config) = CONFIG.get() {
+            return Ok(config.as_ref());
+        }
+        let config = Configuration::load_if_present(Path::new(CONFIG_FILE))?;
+        Ok(CONFIG.get_or_init(|| config).as_ref())
+    }
+
+    /// Loads `toml_file`, or returns `None` if it does not exist.
+    fn load_if_present(toml_file: &Path) -> Result<Option<Configuration>> {
+        let toml_string = match std::fs::read_to_string(toml_file) {
+            Ok(toml_string) => toml_string,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_path_context(toml_file, "loading configuration"),
+        };
+        let config = toml::from_str(&toml_string)
+            .map_err(|e| Error::str(format!("failed to parse {}: {e}", toml_file.display())))?;
+        Ok(Some(config))
+    }
 }
 
 // Goals-specific configuration for markdown processing
@@ -266,6 +410,36 @@ some_other_config = "value"
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_configuration_load_if_present() {
+        let directory = tempfile::tempdir().unwrap();
+        let toml_file = directory.path().join(CONFIG_FILE);
+        assert!(Configuration::load_if_present(&toml_file)
+            .unwrap()
+            .is_none());
+
+        std::fs::write(
+            &toml_file,
+            r#"
+[team_asks]
+
+[github_cache]
+ttl_minutes = 60
+            "#,
+        )
+        .unwrap();
+        let config = Configuration::load_if_present(&toml_file).unwrap().unwrap();
+        assert_eq!(config.github_cache.ttl_minutes, 60);
+        assert_eq!(
+            config.github_cache.directory,
+            GithubCacheConfig::default().directory
+        );
+        assert!(config.templates.directory.is_none());
+
+        std::fs::write(&toml_file, "[github_cache\n").unwrap();
+        assert!(Configuration::load_if_present(&toml_file).is_err());
+    }
+
     #[test]
     fn test_goals_config_default() {
         let config = GoalsConfig::default();
@@ -1,7 +1,10 @@
 //! Code for querying and interacting with github.
 //!
-//! We do most everything through the `gh` command-line tool.
+//! By default we do most everything through the `gh` command-line tool,
+//! but see [`backend`] for talking to the github API directly
+//! or running against recorded fixtures instead.
 
+pub mod backend;
 pub mod issue_id;
 pub mod issues;
 pub mod labels;
@@ -0,0 +1,198 @@
+//! The backend through which we talk to github.
+//!
+//! Every function in [`super::issues`] and [`super::labels`] that reads or modifies
+//! github data goes through the [`GithubBackend`] returned by [`backend`].
+//! By default this is the `gh` command-line tool ([`GhCli`]). It can be replaced with
+//! a native HTTP client ([`GithubApi`]) that does not need `gh` installed, or with
+//! a directory of recorded fixtures ([`Fixtures`]) so that commands can run
+//! offline and in tests. Reads from github are normally kept in a [`Cache`] across runs.
+
+use std::{collections::BTreeSet, path::PathBuf, sync::OnceLock, time::Duration};
+
+use spanned::{Error, Result};
+
+use super::{
+    issue_id::{IssueId, Repository},
+    issues::{CountIssues, CountPrs, ExistingGithubIssue, IssueStateReason},
+    labels::GhLabel,
+};
+
+mod api;
+mod cache;
+mod fixtures;
+mod gh_cli;
+
+pub use api::{GithubApi, GithubApiError, TOKEN_ENV_VARS};
+pub use cache::{cache_stats, clear_cache, Cache, CacheStats};
+pub use fixtures::{Fixtures, Recorder};
+pub use gh_cli::GhCli;
+
+/// If this environment variable is set and no backend was configured explicitly,
+/// it names a fixtures directory that is used instead of the `gh` CLI.
+/// This is how the mdbook preprocessor can be run offline.
+pub const FIXTURES_ENV_VAR: &str = "RUST_PROJECT_GOALS_GITHUB_FIXTURES";
+
+/// If this environment variable is set to `api` and no backend was configured explicitly,
+/// we talk to github over HTTP ([`GithubApi`]) instead of through the `gh` CLI.
+pub const BACKEND_ENV_VAR: &str = "RUST_PROJECT_GOALS_GITHUB_BACKEND";
+
+/// Operations we perform on github.
+pub trait GithubBackend: Send + Sync {
+    /// Report an error if the backend cannot be used (e.g., a required tool is not installed).
+    fn sanity_check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// How long to pause between modifications when executing many of them in a row,
+    /// for backends that cannot handle rate limits themselves.
+    fn pause_between_writes(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>>;
+
+    /// Create the label, or update its color if it already exists.
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> Result<()>;
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues>;
+
+    /// Count the pull requests matching `search`, by whether they are open, merged,
+    /// or closed without being merged.
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs>;
+
+    fn fetch_issue(&self, repository: &Repository, number: u64) -> Result<ExistingGithubIssue>;
+
+    /// Like [`Self::fetch_issue`], but never answered from a cache, for when we are about
+    /// to modify the issue based on what it contains.
+    fn fetch_current_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<ExistingGithubIssue> {
+        self.fetch_issue(repository, number)
+    }
+
+    /// Find out whether the issue changed, without fetching it and all its comments.
+    /// `etag` is from an earlier [`IssueVersion::Current`] for this issue, if any,
+    /// and can be used for a conditional request.
+    /// Returns `None` if the backend cannot do this more cheaply than [`Self::fetch_issue`].
+    fn issue_version(
+        &self,
+        _repository: &Repository,
+        _number: u64,
+        _etag: Option<&str>,
+    ) -> Result<Option<IssueVersion>> {
+        Ok(None)
+    }
+
+    /// All issues (open or closed) in the given milestone.
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>>;
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> Result<IssueId>;
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str) -> Result<()>;
+
+    fn change_milestone(&self, repository: &Repository, number: u64, milestone: &str)
+        -> Result<()>;
+
+    fn create_comment(&self, repository: &Repository, number: u64, body: &str) -> Result<()>;
+
+    fn update_issue_body(&self, repository: &Repository, number: u64, body: &str) -> Result<()>;
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> Result<()>;
+
+    fn sync_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_labels: &BTreeSet<String>,
+        add_labels: &BTreeSet<String>,
+    ) -> Result<()>;
+
+    /// Lock the issue; succeeds if it is already locked.
+    fn lock_issue(&self, repository: &Repository, number: u64) -> Result<()>;
+
+    /// Close the issue, recording `state_reason` if given.
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        state_reason: Option<IssueStateReason>,
+    ) -> Result<()>;
+}
+
+/// The answer to [`GithubBackend::issue_version`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IssueVersion {
+    /// The issue is unchanged since the response with the given `etag`.
+    NotModified,
+
+    /// The issue was last updated at `updated_at` (compare with
+    /// [`ExistingGithubIssue::updated_at`]). `etag` identifies this response
+    /// for the next conditional request.
+    Current {
+        updated_at: String,
+        etag: Option<String>,
+    },
+}
+
+static BACKEND: OnceLock<Box<dyn GithubBackend>> = OnceLock::new();
+
+/// Select the backend to use for all github operations.
+/// This must be called before the first github operation is performed.
+pub fn set_backend(backend: Box<dyn GithubBackend>) -> Result<()> {
+    BACKEND
+        .set(backend)
+        .map_err(|_| Error::str("github backend was already initialized".to_string()))
+}
+
+/// Returns the backend for github operations. If [`set_backend`] was not called,
+/// this is the [`default_backend`].
+pub fn backend() -> &'static dyn GithubBackend {
+    &**BACKEND.get_or_init(default_backend)
+}
+
+/// The backend selected by the environment: the fixtures named by [`FIXTURES_ENV_VAR`],
+/// the HTTP client if [`BACKEND_ENV_VAR`] is `api`, or else the `gh` CLI.
+/// Except for fixtures, which are already local, reads go through a [`Cache`].
+pub fn default_backend() -> Box<dyn GithubBackend> {
+    let backend = uncached_default_backend();
+    if std::env::var_os(FIXTURES_ENV_VAR).is_some() {
+        return backend;
+    }
+    Box::new(Cache::from_config(backend))
+}
+
+/// Like [`default_backend`], but without the [`Cache`].
+pub fn uncached_default_backend() -> Box<dyn GithubBackend> {
+    if let Some(directory) = std::env::var_os(FIXTURES_ENV_VAR) {
+        return Box::new(Fixtures::new(PathBuf::from(directory)));
+    }
+
+    match std::env::var(BACKEND_ENV_VAR).as_deref() {
+        Ok("api") => Box::new(GithubApi::new()),
+        _ => Box::new(GhCli),
+    }
+}
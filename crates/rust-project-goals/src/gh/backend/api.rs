@@ -0,0 +1,1034 @@
+use std::{collections::BTreeSet, fmt, time::Duration};
+
+use reqwest::{
+    blocking::Client,
+    header::{HeaderMap, ACCEPT, ETAG, IF_NONE_MATCH, USER_AGENT},
+    Method, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use spanned::Result;
+
+use crate::gh::{
+    issue_id::{IssueId, Repository},
+    issues::{CountIssues, CountPrs, ExistingGithubComment, ExistingGithubIssue, IssueStateReason},
+    labels::GhLabel,
+    milestone::GhMilestone,
+};
+use crate::util::in_thread;
+use rust_project_goals_json::GithubIssueState;
+
+use super::{GithubBackend, IssueVersion};
+
+const API_URL: &str = "https://api.github.com";
+
+/// Environment variables from which we read the github token, in order of preference.
+pub const TOKEN_ENV_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// How often we send a request before giving up on rate limits or server errors.
+const MAX_ATTEMPTS: u32 = 6;
+
+/// Talks to github directly over HTTP: reads go through the GraphQL API
+/// (so we can page through issues and their comments), writes through the REST API.
+/// Authenticates with a token from [`TOKEN_ENV_VARS`].
+pub struct GithubApi {
+    token: Option<String>,
+    client: Client,
+}
+
+/// Something that went wrong while talking to the github API.
+#[derive(Debug)]
+pub enum GithubApiError {
+    /// None of the [`TOKEN_ENV_VARS`] is set.
+    MissingToken,
+
+    /// The request could not be sent or the response could not be read.
+    Transport {
+        method: Method,
+        url: Url,
+        error: reqwest::Error,
+    },
+
+    /// Github answered with an error status.
+    Status {
+        method: Method,
+        url: Url,
+        status: StatusCode,
+        message: String,
+    },
+
+    /// We were still rate limited (or github kept failing) after [`MAX_ATTEMPTS`] attempts.
+    RetriesExhausted {
+        method: Method,
+        url: Url,
+        status: StatusCode,
+    },
+
+    /// The response did not have the shape we expected.
+    Decode { url: Url, error: serde_json::Error },
+
+    /// A GraphQL query reported errors.
+    GraphQl { messages: Vec<String> },
+
+    /// Something we looked up (e.g., a milestone) does not exist.
+    NotFound { what: String },
+}
+
+impl fmt::Display for GithubApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GithubApiError::MissingToken => write!(
+                f,
+                "no github token found; set one of {}",
+                TOKEN_ENV_VARS.join(", ")
+            ),
+            GithubApiError::Transport { method, url, error } => {
+                write!(f, "{method} {url} failed: {error}")
+            }
+            GithubApiError::Status {
+                method,
+                url,
+                status,
+                message,
+            } => write!(f, "{method} {url} failed with {status}: {message}"),
+            GithubApiError::RetriesExhausted {
+                method,
+                url,
+                status,
+            } => write!(
+                f,
+                "{method} {url} still failed with {status} after {MAX_ATTEMPTS} attempts"
+            ),
+            GithubApiError::Decode { url, error } => {
+                write!(f, "unexpected response from {url}: {error}")
+            }
+            GithubApiError::GraphQl { messages } => {
+                write!(f, "github query failed: {}", messages.join("; "))
+            }
+            GithubApiError::NotFound { what } => write!(f, "{what} not found"),
+        }
+    }
+}
+
+impl std::error::Error for GithubApiError {}
+
+type ApiResult<T> = std::result::Result<T, GithubApiError>;
+
+/// The fields we need from a milestone as the REST API returns it. Unlike [`GhMilestone`],
+/// the description may be `null` there and the due date is spelled `due_on`.
+#[derive(Deserialize)]
+struct RestMilestone {
+    number: u64,
+    title: String,
+}
+
+impl GithubApi {
+    pub fn new() -> Self {
+        let token = TOKEN_ENV_VARS
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+            .filter(|token| !token.is_empty());
+
+        // Create the client on another thread because the blocking reqwest API
+        // creates a tokio runtime (see `team::fetch`).
+        let client = in_thread(Client::new);
+
+        Self { token, client }
+    }
+
+    fn repo_url(&self, repository: &Repository, path: &[&str]) -> Url {
+        let mut url = Url::parse(API_URL).unwrap();
+        url.path_segments_mut()
+            .unwrap()
+            .pop_if_empty()
+            .push("repos")
+            .extend([repository.org.as_str(), repository.repo.as_str()])
+            .extend(path);
+        url
+    }
+
+    /// Send a request, retrying (with backoff) when we hit a rate limit or github has a hiccup.
+    /// Returns the response headers and body of the first successful response.
+    fn request(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&serde_json::Value>,
+    ) -> ApiResult<(HeaderMap, String)> {
+        // Without an `If-None-Match` header, github never answers "not modified".
+        Ok(self
+            .send(method, url, body, None)?
+            .expect("not modified without an etag"))
+    }
+
+    /// Like [`Self::request`], but if `etag` is given, asks github to only send the response
+    /// if it changed since the response with that `ETag`. Returns `None` if it did not.
+    /// Such requests do not count against the rate limit.
+    fn send(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&serde_json::Value>,
+        etag: Option<&str>,
+    ) -> ApiResult<Option<(HeaderMap, String)>> {
+        let token = self.token.as_ref().ok_or(GithubApiError::MissingToken)?;
+
+        let mut attempt = 0;
+        loop {
+            // See `GithubApi::new` for why this runs on another thread.
+            let (status, headers, text) = in_thread(|| -> reqwest::Result<_> {
+                let mut request = self
+                    .client
+                    .request(method.clone(), url.clone())
+                    .bearer_auth(token)
+                    .header(USER_AGENT, "rust-project-goals")
+                    .header(ACCEPT, "application/vnd.github+json")
+                    .header("X-GitHub-Api-Version", "2022-11-28");
+                if let Some(body) = body {
+                    request = request.json(body);
+                }
+                if let Some(etag) = etag {
+                    request = request.header(IF_NONE_MATCH, etag);
+                }
+                let response = request.send()?;
+                let status = response.status();
+                let headers = response.headers().clone();
+                Ok((status, headers, response.text()?))
+            })
+            .map_err(|error| GithubApiError::Transport {
+                method: method.clone(),
+                url: url.clone(),
+                error,
+            })?;
+
+            if status == StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+            if status.is_success() {
+                return Ok(Some((headers, text)));
+            }
+
+            let wait = match rate_limit_wait(status, &headers, attempt) {
+                Some(wait) => wait,
+                None if status.is_server_error() => backoff(attempt),
+                None => {
+                    return Err(GithubApiError::Status {
+                        method,
+                        url: url.clone(),
+                        status,
+                        message: error_message(&text),
+                    })
+                }
+            };
+
+            attempt += 1;
+            if attempt == MAX_ATTEMPTS {
+                return Err(GithubApiError::RetriesExhausted {
+                    method,
+                    url: url.clone(),
+                    status,
+                });
+            }
+
+            eprintln!(
+                "github responded with {status}, retrying in {}s",
+                wait.as_secs()
+            );
+            std::thread::sleep(wait);
+        }
+    }
+
+    fn rest(&self, method: Method, url: &Url, body: Option<serde_json::Value>) -> ApiResult<()> {
+        self.request(method, url, body.as_ref())?;
+        Ok(())
+    }
+
+    /// GET a REST endpoint that returns a list, following the `Link` headers through all pages.
+    fn rest_paginated<T: DeserializeOwned>(&self, url: Url) -> ApiResult<Vec<T>> {
+        let mut url = url;
+        url.query_pairs_mut().append_pair("per_page", "100");
+
+        let mut items = vec![];
+        loop {
+            let (headers, text) = self.request(Method::GET, &url, None)?;
+            let page: Vec<T> = decode(&url, &text)?;
+            items.extend(page);
+
+            match next_page(&headers) {
+                Some(next) => url = next,
+                None => return Ok(items),
+            }
+        }
+    }
+
+    fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> ApiResult<T> {
+        #[derive(Deserialize)]
+        struct GraphQlResponse<T> {
+            data: Option<T>,
+            #[serde(default)]
+            errors: Vec<GraphQlError>,
+        }
+
+        #[derive(Deserialize)]
+        struct GraphQlError {
+            message: String,
+            #[serde(rename = "type")]
+            kind: Option<String>,
+        }
+
+        let url = Url::parse(API_URL).unwrap().join("graphql").unwrap();
+        let body = json!({ "query": query, "variables": variables });
+
+        let mut attempt = 0;
+        loop {
+            let (_, text) = self.request(Method::POST, &url, Some(&body))?;
+            let response: GraphQlResponse<T> = decode(&url, &text)?;
+
+            // GraphQL reports rate limits in the response body rather than the status.
+            let rate_limited = response
+                .errors
+                .iter()
+                .any(|e| e.kind.as_deref() == Some("RATE_LIMITED"));
+            // On the last attempt, the rate limit is reported like any other query error.
+            if rate_limited && attempt + 1 < MAX_ATTEMPTS {
+                let wait = backoff(attempt);
+                eprintln!("github query rate limited, retrying in {}s", wait.as_secs());
+                std::thread::sleep(wait);
+                attempt += 1;
+                continue;
+            }
+
+            return match response.data {
+                Some(data) if response.errors.is_empty() => Ok(data),
+                _ => Err(GithubApiError::GraphQl {
+                    messages: response.errors.into_iter().map(|e| e.message).collect(),
+                }),
+            };
+        }
+    }
+
+    fn milestone_number(&self, repository: &Repository, title: &str) -> ApiResult<u64> {
+        let mut url = self.repo_url(repository, &["milestones"]);
+        url.query_pairs_mut().append_pair("state", "all");
+        let milestones: Vec<RestMilestone> = self.rest_paginated(url)?;
+        milestones
+            .into_iter()
+            .find(|m| m.title == title)
+            .map(|m| m.number)
+            .ok_or_else(|| GithubApiError::NotFound {
+                what: format!("milestone `{title}` in `{repository}`"),
+            })
+    }
+
+    fn issue_url(&self, repository: &Repository, number: u64, path: &[&str]) -> Url {
+        let number = number.to_string();
+        let mut segments = vec!["issues", number.as_str()];
+        segments.extend(path);
+        self.repo_url(repository, &segments)
+    }
+
+    /// Fetch the comments on `number` that come after `cursor`.
+    fn remaining_comments(
+        &self,
+        repository: &Repository,
+        number: u64,
+        mut cursor: Option<String>,
+    ) -> ApiResult<Vec<CommentNode>> {
+        #[derive(Deserialize)]
+        struct Data {
+            repository: CommentsRepository,
+        }
+
+        #[derive(Deserialize)]
+        struct CommentsRepository {
+            issue: Option<CommentsIssue>,
+        }
+
+        #[derive(Deserialize)]
+        struct CommentsIssue {
+            comments: Connection<CommentNode>,
+        }
+
+        let mut comments = vec![];
+        while let Some(after) = cursor {
+            let data: Data = self.graphql(
+                COMMENTS_QUERY,
+                json!({
+                    "owner": repository.org,
+                    "repo": repository.repo,
+                    "number": number,
+                    "after": after,
+                }),
+            )?;
+            let Some(issue) = data.repository.issue else {
+                break;
+            };
+            cursor = issue.comments.page_info.next_cursor();
+            comments.extend(issue.comments.nodes);
+        }
+        Ok(comments)
+    }
+
+    /// Convert an issue from a GraphQL response, fetching any comments that did not fit in it.
+    fn complete_issue(
+        &self,
+        repository: &Repository,
+        node: IssueNode,
+    ) -> ApiResult<ExistingGithubIssue> {
+        let mut comments = node.comments.nodes;
+        comments.extend(self.remaining_comments(
+            repository,
+            node.number,
+            node.comments.page_info.next_cursor(),
+        )?);
+
+        Ok(ExistingGithubIssue {
+            number: node.number,
+            title: node.title,
+            assignees: node.assignees.nodes.into_iter().map(|a| a.login).collect(),
+            comments: comments
+                .into_iter()
+                .map(|c| ExistingGithubComment {
+                    author: format!(
+                        "@{}",
+                        c.author
+                            .map(|a| a.login)
+                            .unwrap_or_else(|| "ghost".to_string())
+                    ),
+                    body: c.body,
+                    created_at: c.created_at,
+                    url: c.url,
+                    hidden: c.is_minimized,
+                })
+                .collect(),
+            body: node.body,
+            state: node.state,
+            labels: node.labels.nodes,
+            milestone: node.milestone.map(|m| GhMilestone {
+                number: m.number,
+                title: m.title,
+                description: m.description.unwrap_or_default(),
+                due_on: m.due_on,
+            }),
+            updated_at: Some(node.updated_at),
+        })
+    }
+}
+
+impl GithubBackend for GithubApi {
+    fn sanity_check(&self) -> Result<()> {
+        if self.token.is_none() {
+            Err(GithubApiError::MissingToken)?;
+        }
+        Ok(())
+    }
+
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>> {
+        Ok(self.rest_paginated(self.repo_url(repository, &["labels"]))?)
+    }
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> Result<()> {
+        let result = self.rest(
+            Method::POST,
+            &self.repo_url(repository, &["labels"]),
+            Some(json!({ "name": label.name, "color": label.color })),
+        );
+
+        match result {
+            // The label already exists: update its color instead.
+            Err(GithubApiError::Status {
+                status: StatusCode::UNPROCESSABLE_ENTITY,
+                ..
+            }) => Ok(self.rest(
+                Method::PATCH,
+                &self.repo_url(repository, &["labels", label.name.as_str()]),
+                Some(json!({ "color": label.color })),
+            )?),
+            result => Ok(result?),
+        }
+    }
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues> {
+        #[derive(Deserialize)]
+        struct Data {
+            open: SearchCount,
+            closed: SearchCount,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SearchCount {
+            issue_count: u32,
+        }
+
+        let data: Data = self.graphql(
+            SEARCH_QUERY,
+            json!({
+                "open": format!("repo:{repository} is:issue is:open {search}"),
+                "closed": format!("repo:{repository} is:issue is:closed {search}"),
+            }),
+        )?;
+
+        Ok(CountIssues {
+            open: data.open.issue_count,
+            closed: data.closed.issue_count,
+        })
+    }
+
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs> {
+        #[derive(Deserialize)]
+        struct Data {
+            open: SearchCount,
+            merged: SearchCount,
+            closed: SearchCount,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SearchCount {
+            issue_count: u32,
+        }
+
+        let data: Data = self.graphql(
+            PR_SEARCH_QUERY,
+            json!({
+                "open": format!("repo:{repository} is:pr is:open {search}"),
+                "merged": format!("repo:{repository} is:pr is:merged {search}"),
+                "closed": format!("repo:{repository} is:pr is:closed is:unmerged {search}"),
+            }),
+        )?;
+
+        Ok(CountPrs {
+            open: data.open.issue_count,
+            merged: data.merged.issue_count,
+            closed: data.closed.issue_count,
+        })
+    }
+
+    fn fetch_issue(&self, repository: &Repository, number: u64) -> Result<ExistingGithubIssue> {
+        #[derive(Deserialize)]
+        struct Data {
+            repository: IssueRepository,
+        }
+
+        #[derive(Deserialize)]
+        struct IssueRepository {
+            issue: Option<IssueNode>,
+        }
+
+        let data: Data = self.graphql(
+            &format!("{ISSUE_QUERY}{ISSUE_FIELDS}"),
+            json!({
+                "owner": repository.org,
+                "repo": repository.repo,
+                "number": number,
+            }),
+        )?;
+
+        let Some(node) = data.repository.issue else {
+            spanned::bail_here!("fetching `{repository}` issue {number} failed: not found");
+        };
+
+        Ok(self.complete_issue(repository, node)?)
+    }
+
+    fn issue_version(
+        &self,
+        repository: &Repository,
+        number: u64,
+        etag: Option<&str>,
+    ) -> Result<Option<IssueVersion>> {
+        #[derive(Deserialize)]
+        struct JustUpdatedAt {
+            updated_at: String,
+        }
+
+        // Unlike the GraphQL API, the REST API supports conditional requests.
+        let url = self.issue_url(repository, number, &[]);
+        let Some((headers, text)) = self.send(Method::GET, &url, None, etag)? else {
+            return Ok(Some(IssueVersion::NotModified));
+        };
+        let issue: JustUpdatedAt = decode(&url, &text)?;
+
+        Ok(Some(IssueVersion::Current {
+            updated_at: issue.updated_at,
+            etag: headers
+                .get(ETAG)
+                .and_then(|etag| etag.to_str().ok())
+                .map(|etag| etag.to_string()),
+        }))
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>> {
+        #[derive(Deserialize)]
+        struct Data {
+            repository: IssuesRepository,
+        }
+
+        #[derive(Deserialize)]
+        struct IssuesRepository {
+            issues: Connection<IssueNode>,
+        }
+
+        let milestone_number = self.milestone_number(repository, milestone)?;
+
+        let mut issues = vec![];
+        let mut cursor = None;
+        loop {
+            let data: Data = self.graphql(
+                &format!("{MILESTONE_ISSUES_QUERY}{ISSUE_FIELDS}"),
+                json!({
+                    "owner": repository.org,
+                    "repo": repository.repo,
+                    "milestone": milestone_number.to_string(),
+                    "after": cursor,
+                }),
+            )?;
+
+            let page = data.repository.issues;
+            for node in page.nodes {
+                issues.push(self.complete_issue(repository, node)?);
+            }
+
+            cursor = page.page_info.next_cursor();
+            if cursor.is_none() {
+                return Ok(issues);
+            }
+        }
+    }
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> Result<IssueId> {
+        #[derive(Deserialize)]
+        struct CreatedIssue {
+            number: u64,
+        }
+
+        let milestone = self.milestone_number(repository, milestone)?;
+        let url = self.repo_url(repository, &["issues"]);
+        let (_, text) = self.request(
+            Method::POST,
+            &url,
+            Some(&json!({
+                "title": title,
+                "body": body,
+                "labels": labels,
+                "assignees": assignees,
+                "milestone": milestone,
+            })),
+        )?;
+        let created: CreatedIssue = decode(&url, &text)?;
+
+        Ok(IssueId::new(repository.clone(), created.number))
+    }
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str) -> Result<()> {
+        Ok(self.rest(
+            Method::PATCH,
+            &self.issue_url(repository, number, &[]),
+            Some(json!({ "title": title })),
+        )?)
+    }
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> Result<()> {
+        let milestone = self.milestone_number(repository, milestone)?;
+        Ok(self.rest(
+            Method::PATCH,
+            &self.issue_url(repository, number, &[]),
+            Some(json!({ "milestone": milestone })),
+        )?)
+    }
+
+    fn create_comment(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        Ok(self.rest(
+            Method::POST,
+            &self.issue_url(repository, number, &["comments"]),
+            Some(json!({ "body": body })),
+        )?)
+    }
+
+    fn update_issue_body(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        Ok(self.rest(
+            Method::PATCH,
+            &self.issue_url(repository, number, &[]),
+            Some(json!({ "body": body })),
+        )?)
+    }
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> Result<()> {
+        let url = self.issue_url(repository, number, &["assignees"]);
+
+        if !remove_owners.is_empty() {
+            self.rest(
+                Method::DELETE,
+                &url,
+                Some(json!({ "assignees": remove_owners })),
+            )?;
+        }
+
+        if !add_owners.is_empty() {
+            self.rest(Method::POST, &url, Some(json!({ "assignees": add_owners })))?;
+        }
+
+        Ok(())
+    }
+
+    fn sync_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_labels: &BTreeSet<String>,
+        add_labels: &BTreeSet<String>,
+    ) -> Result<()> {
+        for label in remove_labels {
+            self.rest(
+                Method::DELETE,
+                &self.issue_url(repository, number, &["labels", label.as_str()]),
+                None,
+            )?;
+        }
+
+        if !add_labels.is_empty() {
+            self.rest(
+                Method::POST,
+                &self.issue_url(repository, number, &["labels"]),
+                Some(json!({ "labels": add_labels })),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> Result<()> {
+        // Locking an issue that is already locked succeeds.
+        Ok(self.rest(
+            Method::PUT,
+            &self.issue_url(repository, number, &["lock"]),
+            Some(json!({})),
+        )?)
+    }
+
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        state_reason: Option<IssueStateReason>,
+    ) -> Result<()> {
+        let mut body = json!({ "state": "closed" });
+        if let Some(state_reason) = state_reason {
+            body["state_reason"] = json!(state_reason);
+        }
+        Ok(self.rest(
+            Method::PATCH,
+            &self.issue_url(repository, number, &[]),
+            Some(body),
+        )?)
+    }
+}
+
+/// If the response indicates we hit a rate limit, how long to wait before trying again.
+fn rate_limit_wait(status: StatusCode, headers: &HeaderMap, attempt: u32) -> Option<Duration> {
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    let header = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+
+    // Secondary rate limits tell us how long to wait.
+    if let Some(seconds) = header("retry-after") {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    // Primary rate limits tell us when the limit resets.
+    if header("x-ratelimit-remaining") == Some(0) {
+        let reset = header("x-ratelimit-reset")?;
+        let now = chrono::Utc::now().timestamp() as u64;
+        return Some(Duration::from_secs(reset.saturating_sub(now) + 1));
+    }
+
+    // A 403 without rate limit headers is a real permission error.
+    if status == StatusCode::TOO_MANY_REQUESTS {
+        Some(backoff(attempt))
+    } else {
+        None
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << attempt)
+}
+
+/// The url of the next page from a `Link` header like `<https://...>; rel="next", <...>; rel="last"`.
+fn next_page(headers: &HeaderMap) -> Option<Url> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, rel) = part.split_once(';')?;
+        if rel.trim() != "rel=\"next\"" {
+            return None;
+        }
+        Url::parse(url.trim().trim_start_matches('<').trim_end_matches('>')).ok()
+    })
+}
+
+/// Extract the `message` from a github error response, falling back to the raw body.
+fn error_message(text: &str) -> String {
+    #[derive(Deserialize)]
+    struct ErrorResponse {
+        message: String,
+    }
+
+    match serde_json::from_str::<ErrorResponse>(text) {
+        Ok(e) => e.message,
+        Err(_) => text.to_string(),
+    }
+}
+
+fn decode<T: DeserializeOwned>(url: &Url, text: &str) -> ApiResult<T> {
+    serde_json::from_str(text).map_err(|error| GithubApiError::Decode {
+        url: url.clone(),
+        error,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    page_info: PageInfo,
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+impl PageInfo {
+    fn next_cursor(&self) -> Option<String> {
+        if self.has_next_page {
+            self.end_cursor.clone()
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct LoginNode {
+    login: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueNode {
+    number: u64,
+    title: String,
+    body: String,
+    state: GithubIssueState,
+    assignees: Nodes<LoginNode>,
+    labels: Nodes<GhLabel>,
+    milestone: Option<MilestoneNode>,
+    comments: Connection<CommentNode>,
+    updated_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MilestoneNode {
+    number: u64,
+    title: String,
+    description: Option<String>,
+    due_on: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentNode {
+    /// `None` if the account was deleted.
+    author: Option<LoginNode>,
+    body: String,
+    created_at: String,
+    url: String,
+    /// Whether a comment was marked "hidden" on the GH UI.
+    is_minimized: bool,
+}
+
+const ISSUE_FIELDS: &str = r#"
+fragment IssueFields on Issue {
+  number
+  title
+  body
+  state
+  assignees(first: 100) { nodes { login } }
+  labels(first: 100) { nodes { name color } }
+  milestone { number title description dueOn }
+  updatedAt
+  comments(first: 100) {
+    pageInfo { hasNextPage endCursor }
+    nodes { author { login } body createdAt url isMinimized }
+  }
+}
+"#;
+
+const MILESTONE_ISSUES_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $milestone: String!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    issues(first: 50, after: $after, filterBy: { milestoneNumber: $milestone }) {
+      pageInfo { hasNextPage endCursor }
+      nodes { ...IssueFields }
+    }
+  }
+}
+"#;
+
+const ISSUE_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    issue(number: $number) { ...IssueFields }
+  }
+}
+"#;
+
+const COMMENTS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    issue(number: $number) {
+      comments(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes { author { login } body createdAt url isMinimized }
+      }
+    }
+  }
+}
+"#;
+
+const SEARCH_QUERY: &str = r#"
+query($open: String!, $closed: String!) {
+  open: search(query: $open, type: ISSUE) { issueCount }
+  closed: search(query: $closed, type: ISSUE) { issueCount }
+}
+"#;
+
+const PR_SEARCH_QUERY: &str = r#"
+query($open: String!, $merged: String!, $closed: String!) {
+  open: search(query: $open, type: ISSUE) { issueCount }
+  merged: search(query: $merged, type: ISSUE) { issueCount }
+  closed: search(query: $closed, type: ISSUE) { issueCount }
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_next_page() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            HeaderValue::from_static(
+                "<https://api.github.com/repositories/1/labels?per_page=100&page=2>; rel=\"next\", \
+                 <https://api.github.com/repositories/1/labels?per_page=100&page=3>; rel=\"last\"",
+            ),
+        );
+        assert_eq!(
+            next_page(&headers).unwrap().as_str(),
+            "https://api.github.com/repositories/1/labels?per_page=100&page=2"
+        );
+
+        headers.insert(
+            "link",
+            HeaderValue::from_static(
+                "<https://api.github.com/repositories/1/labels?per_page=100&page=1>; rel=\"prev\"",
+            ),
+        );
+        assert!(next_page(&headers).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(rate_limit_wait(StatusCode::FORBIDDEN, &headers, 0), None);
+        assert_eq!(
+            rate_limit_wait(StatusCode::TOO_MANY_REQUESTS, &headers, 2),
+            Some(Duration::from_secs(4))
+        );
+
+        headers.insert("retry-after", HeaderValue::from_static("60"));
+        assert_eq!(
+            rate_limit_wait(StatusCode::FORBIDDEN, &headers, 0),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(rate_limit_wait(StatusCode::NOT_FOUND, &headers, 0), None);
+    }
+
+    #[test]
+    fn test_decode_rest_milestones() {
+        let url =
+            Url::parse("https://api.github.com/repos/rust-lang/rust-project-goals/milestones")
+                .unwrap();
+        let text = r#"[
+            {"number": 1, "title": "2024h2", "description": null, "due_on": null, "state": "closed"},
+            {"number": 2, "title": "2025h1", "description": "", "due_on": "2025-06-30T07:00:00Z", "state": "open"}
+        ]"#;
+        let milestones: Vec<RestMilestone> = decode(&url, text).unwrap();
+        let milestones: Vec<(u64, &str)> = milestones
+            .iter()
+            .map(|m| (m.number, m.title.as_str()))
+            .collect();
+        assert_eq!(milestones, vec![(1, "2024h2"), (2, "2025h1")]);
+    }
+
+    #[test]
+    fn test_label_url_is_escaped() {
+        let api = GithubApi {
+            token: None,
+            client: Client::new(),
+        };
+        let url = api.repo_url(
+            &Repository::new("rust-lang", "rust-project-goals"),
+            &["labels", "Flagship Goal"],
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://api.github.com/repos/rust-lang/rust-project-goals/labels/Flagship%20Goal"
+        );
+    }
+}
@@ -0,0 +1,547 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    collections::BTreeSet,
+    ffi::OsStr,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, de::IgnoredAny, Deserialize, Serialize};
+use spanned::{Context, Result};
+use walkdir::WalkDir;
+
+use crate::config::GithubCacheConfig;
+use crate::gh::{
+    issue_id::{IssueId, Repository},
+    issues::{CountIssues, CountPrs, ExistingGithubIssue, IssueStateReason},
+    labels::GhLabel,
+};
+
+use super::{GithubBackend, IssueVersion};
+
+/// Wraps another backend and keeps what is read through it in a directory, across runs:
+///
+/// * `<dir>/<org>/<repo>/issues/<number>.json` for each issue,
+/// * `<dir>/<org>/<repo>/milestones/<milestone>.json` with the numbers of the issues in a milestone,
+/// * `<dir>/<org>/<repo>/searches/<hash>.json` for the counts of an issue or PR search.
+///
+/// Entries younger than the TTL are used as they are. Older issues are revalidated with
+/// [`GithubBackend::issue_version`] (a conditional request, where supported) and only fetched
+/// again if they changed; older milestones and searches are fetched again.
+/// Any modification made through the cache invalidates what it may have changed.
+pub struct Cache {
+    inner: Box<dyn GithubBackend>,
+    directory: PathBuf,
+    ttl: Duration,
+}
+
+/// A value in the cache.
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    /// When the value was fetched or last confirmed to be current, in seconds since the unix epoch
+    checked_at: u64,
+
+    /// For issues, the `ETag` from the last time we confirmed that the issue is current
+    #[serde(default)]
+    etag: Option<String>,
+
+    /// For searches, the search itself (the file name is only a hash of it),
+    /// prefixed with `is:pr` for PR searches
+    #[serde(default)]
+    key: Option<String>,
+
+    value: T,
+}
+
+impl<T> Entry<T> {
+    fn new(value: T) -> Self {
+        Entry {
+            checked_at: now(),
+            etag: None,
+            key: None,
+            value,
+        }
+    }
+
+    fn is_fresh(&self, ttl: Duration) -> bool {
+        now().saturating_sub(self.checked_at) < ttl.as_secs()
+    }
+}
+
+/// What is in a cache directory (see `cargo rpg cache stats`).
+#[derive(Debug, Default)]
+pub struct CacheStats {
+    pub issues: usize,
+    pub milestones: usize,
+    pub searches: usize,
+
+    /// How many of the entries are older than the TTL, and will be revalidated or fetched again
+    pub stale: usize,
+
+    /// Total size of the entries
+    pub bytes: u64,
+}
+
+impl Cache {
+    pub fn new(
+        inner: Box<dyn GithubBackend>,
+        directory: impl Into<PathBuf>,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            inner,
+            directory: directory.into(),
+            ttl,
+        }
+    }
+
+    /// A cache in the directory and with the TTL from the `[github_cache]` table of the
+    /// `rust-project-goals.toml` in the current directory, or the defaults if there is none.
+    /// If that file cannot be loaded, we warn and use the defaults as well.
+    pub fn from_config(inner: Box<dyn GithubBackend>) -> Self {
+        let config = GithubCacheConfig::from_working_directory().unwrap_or_else(|e| {
+            eprintln!("warning: using the default github cache settings: {e:?}");
+            GithubCacheConfig::default()
+        });
+        Self::new(inner, &config.directory, config.ttl())
+    }
+
+    fn repository_directory(&self, repository: &Repository) -> PathBuf {
+        self.directory.join(&repository.org).join(&repository.repo)
+    }
+
+    fn issue_path(&self, repository: &Repository, number: u64) -> PathBuf {
+        self.repository_directory(repository)
+            .join("issues")
+            .join(format!("{number}.json"))
+    }
+
+    fn milestone_path(&self, repository: &Repository, milestone: &str) -> PathBuf {
+        self.repository_directory(repository)
+            .join("milestones")
+            .join(format!("{milestone}.json"))
+    }
+
+    fn search_path(&self, repository: &Repository, search: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        search.hash(&mut hasher);
+        self.repository_directory(repository)
+            .join("searches")
+            .join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    /// Forget the issue `number` (if given), and the milestones and searches of `repository`,
+    /// which may have changed along with it.
+    fn invalidate(&self, repository: &Repository, number: Option<u64>) {
+        let directory = self.repository_directory(repository);
+        let mut paths = vec![directory.join("milestones"), directory.join("searches")];
+        paths.extend(number.map(|number| self.issue_path(repository, number)));
+
+        for path in paths {
+            let result = if path.is_dir() {
+                std::fs::remove_dir_all(&path)
+            } else if path.exists() {
+                std::fs::remove_file(&path)
+            } else {
+                continue;
+            };
+            if let Err(e) = result {
+                eprintln!(
+                    "warning: failed to remove `{}` from the github cache: {e}",
+                    path.display()
+                );
+            }
+        }
+    }
+
+    /// The counts for the search `key`, from the cache if they are fresh or from `fetch` otherwise.
+    fn cached_search<T: Serialize + DeserializeOwned>(
+        &self,
+        repository: &Repository,
+        key: String,
+        fetch: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let path = self.search_path(repository, &key);
+        if let Some(entry) = read_entry::<T>(&path) {
+            if entry.key.as_deref() == Some(&key) && entry.is_fresh(self.ttl) {
+                return Ok(entry.value);
+            }
+        }
+
+        let count = fetch()?;
+        write_entry(
+            &path,
+            &Entry {
+                key: Some(key),
+                ..Entry::new(&count)
+            },
+        );
+        Ok(count)
+    }
+
+    /// Stores `issue`, which was just fetched.
+    fn store_issue(&self, repository: &Repository, issue: &ExistingGithubIssue) {
+        write_entry(
+            &self.issue_path(repository, issue.number),
+            &Entry::new(issue),
+        );
+    }
+}
+
+impl GithubBackend for Cache {
+    fn sanity_check(&self) -> Result<()> {
+        self.inner.sanity_check()
+    }
+
+    fn pause_between_writes(&self) -> Duration {
+        self.inner.pause_between_writes()
+    }
+
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>> {
+        self.inner.list_labels(repository)
+    }
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> Result<()> {
+        self.inner.create_label(repository, label)
+    }
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues> {
+        self.cached_search(repository, search.to_string(), || {
+            self.inner.count_issues_matching_search(repository, search)
+        })
+    }
+
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs> {
+        self.cached_search(repository, format!("is:pr {search}"), || {
+            self.inner.count_prs_matching_search(repository, search)
+        })
+    }
+
+    fn fetch_issue(&self, repository: &Repository, number: u64) -> Result<ExistingGithubIssue> {
+        let path = self.issue_path(repository, number);
+        if let Some(mut entry) = read_entry::<ExistingGithubIssue>(&path) {
+            if entry.is_fresh(self.ttl) {
+                return Ok(entry.value);
+            }
+
+            let version = self
+                .inner
+                .issue_version(repository, number, entry.etag.as_deref())?;
+            let unchanged = match version {
+                Some(IssueVersion::NotModified) => true,
+                Some(IssueVersion::Current { updated_at, etag }) => {
+                    // Only keep the etag if it belongs to the issue we have.
+                    let unchanged = entry.value.updated_at.as_ref() == Some(&updated_at);
+                    entry.etag = etag.filter(|_| unchanged);
+                    unchanged
+                }
+                None => false,
+            };
+            if unchanged {
+                entry.checked_at = now();
+                write_entry(&path, &entry);
+                return Ok(entry.value);
+            }
+        }
+
+        let issue = self.inner.fetch_issue(repository, number)?;
+        self.store_issue(repository, &issue);
+        Ok(issue)
+    }
+
+    fn issue_version(
+        &self,
+        repository: &Repository,
+        number: u64,
+        etag: Option<&str>,
+    ) -> Result<Option<IssueVersion>> {
+        self.inner.issue_version(repository, number, etag)
+    }
+
+    fn fetch_current_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<ExistingGithubIssue> {
+        let issue = self.inner.fetch_current_issue(repository, number)?;
+        self.store_issue(repository, &issue);
+        Ok(issue)
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>> {
+        let path = self.milestone_path(repository, milestone);
+        if let Some(entry) = read_entry::<Vec<u64>>(&path) {
+            if entry.is_fresh(self.ttl) {
+                let issues: Option<Vec<ExistingGithubIssue>> = entry
+                    .value
+                    .iter()
+                    .map(|&number| {
+                        read_entry::<ExistingGithubIssue>(&self.issue_path(repository, number))
+                            .map(|entry| entry.value)
+                    })
+                    .collect();
+                if let Some(issues) = issues {
+                    return Ok(issues);
+                }
+            }
+        }
+
+        let issues = self.inner.list_issues_in_milestone(repository, milestone)?;
+        for issue in &issues {
+            self.store_issue(repository, issue);
+        }
+        let numbers: Vec<u64> = issues.iter().map(|issue| issue.number).collect();
+        write_entry(&path, &Entry::new(numbers));
+        Ok(issues)
+    }
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> Result<IssueId> {
+        let result = self
+            .inner
+            .create_issue(repository, body, title, labels, assignees, milestone);
+        self.invalidate(repository, None);
+        result
+    }
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str) -> Result<()> {
+        let result = self.inner.change_title(repository, number, title);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> Result<()> {
+        let result = self.inner.change_milestone(repository, number, milestone);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn create_comment(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        let result = self.inner.create_comment(repository, number, body);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn update_issue_body(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        let result = self.inner.update_issue_body(repository, number, body);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> Result<()> {
+        let result = self
+            .inner
+            .sync_assignees(repository, number, remove_owners, add_owners);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn sync_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_labels: &BTreeSet<String>,
+        add_labels: &BTreeSet<String>,
+    ) -> Result<()> {
+        let result = self
+            .inner
+            .sync_labels(repository, number, remove_labels, add_labels);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> Result<()> {
+        let result = self.inner.lock_issue(repository, number);
+        self.invalidate(repository, Some(number));
+        result
+    }
+
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        state_reason: Option<IssueStateReason>,
+    ) -> Result<()> {
+        let result = self.inner.close_issue(repository, number, state_reason);
+        self.invalidate(repository, Some(number));
+        result
+    }
+}
+
+/// Counts the entries in the cache `directory`; those older than `ttl` are stale.
+pub fn cache_stats(directory: &Path, ttl: Duration) -> Result<CacheStats> {
+    let mut stats = CacheStats::default();
+    if !directory.exists() {
+        return Ok(stats);
+    }
+
+    for entry in WalkDir::new(directory) {
+        let entry = entry?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension() != Some(OsStr::new("json")) {
+            continue;
+        }
+
+        match path.parent().and_then(|p| p.file_name()) {
+            Some(kind) if kind == "issues" => stats.issues += 1,
+            Some(kind) if kind == "milestones" => stats.milestones += 1,
+            Some(kind) if kind == "searches" => stats.searches += 1,
+            _ => continue,
+        }
+        stats.bytes += entry.metadata()?.len();
+        if !read_entry::<IgnoredAny>(path).is_some_and(|entry| entry.is_fresh(ttl)) {
+            stats.stale += 1;
+        }
+    }
+    Ok(stats)
+}
+
+/// Removes everything in the cache `directory`.
+pub fn clear_cache(directory: &Path) -> Result<()> {
+    if directory.exists() {
+        std::fs::remove_dir_all(directory)
+            .with_path_context(directory, "failed to clear the github cache")?;
+    }
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Reads an entry, treating an entry that is missing or cannot be read
+/// (e.g., from an older version of this tool) as not cached.
+fn read_entry<T: DeserializeOwned>(path: &Path) -> Option<Entry<T>> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Writes an entry. Failing to write to the cache is not fatal, so only warns.
+fn write_entry<T: Serialize>(path: &Path, entry: &Entry<T>) {
+    if let Err(e) = try_write_entry(path, entry) {
+        eprintln!(
+            "warning: failed to write `{}` to the github cache: {e:?}",
+            path.display()
+        );
+    }
+}
+
+fn try_write_entry<T: Serialize>(path: &Path, entry: &Entry<T>) -> Result<()> {
+    static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+    let directory = path.parent().unwrap();
+    std::fs::create_dir_all(directory)
+        .with_path_context(directory, "failed to create directory")?;
+
+    // Write to a temporary file and rename it, so that readers never see a partial entry.
+    let temp_path = path.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&temp_path, serde_json::to_string_pretty(entry)?)
+        .with_path_context(&temp_path, "failed to write")?;
+    std::fs::rename(&temp_path, path).with_path_context(path, "failed to write")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gh::backend::Fixtures;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let fixtures = tempfile::tempdir().unwrap();
+        let cache_directory = tempfile::tempdir().unwrap();
+        let repository = Repository::new("rust-lang", "rust-project-goals");
+
+        let cache = Cache::new(
+            Box::new(Fixtures::new(fixtures.path())),
+            cache_directory.path(),
+            Duration::from_secs(60 * 60),
+        );
+        let issue_id = cache
+            .create_issue(&repository, "body", "A goal", &[], &BTreeSet::new(), "2026")
+            .unwrap();
+        assert_eq!(
+            cache
+                .fetch_issue(&repository, issue_id.number)
+                .unwrap()
+                .title,
+            "A goal"
+        );
+        assert_eq!(
+            cache
+                .list_issues_in_milestone(&repository, "2026")
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Changes made behind the cache's back are not seen while the entries are fresh...
+        Fixtures::new(fixtures.path())
+            .change_title(&repository, issue_id.number, "Changed elsewhere")
+            .unwrap();
+        assert_eq!(
+            cache
+                .fetch_issue(&repository, issue_id.number)
+                .unwrap()
+                .title,
+            "A goal"
+        );
+
+        // ...but changes made through it are.
+        cache
+            .change_title(&repository, issue_id.number, "A better goal")
+            .unwrap();
+        assert_eq!(
+            cache
+                .fetch_issue(&repository, issue_id.number)
+                .unwrap()
+                .title,
+            "A better goal"
+        );
+
+        let stats = cache_stats(cache_directory.path(), Duration::from_secs(60 * 60)).unwrap();
+        assert_eq!((stats.issues, stats.milestones, stats.stale), (1, 0, 0));
+
+        clear_cache(cache_directory.path()).unwrap();
+        assert_eq!(
+            cache_stats(cache_directory.path(), Duration::ZERO)
+                .unwrap()
+                .issues,
+            0
+        );
+    }
+}
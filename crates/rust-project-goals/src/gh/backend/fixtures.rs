@@ -0,0 +1,614 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use spanned::{Context, Result};
+
+use crate::gh::{
+    issue_id::{IssueId, Repository},
+    issues::{CountIssues, CountPrs, ExistingGithubComment, ExistingGithubIssue, IssueStateReason},
+    labels::GhLabel,
+    milestone::GhMilestone,
+};
+use rust_project_goals_json::GithubIssueState;
+
+use super::GithubBackend;
+
+/// Author recorded for comments created through the fixtures backend.
+const FIXTURES_AUTHOR: &str = "@rust-project-goals-fixtures";
+
+/// Reads (and writes) github data from a directory of JSON files
+/// instead of talking to github. The layout of the directory is
+///
+/// ```text
+/// <org>/<repo>/issues/<number>.json   -- one `ExistingGithubIssue` per file
+/// <org>/<repo>/labels.json            -- list of `GhLabel`
+/// <org>/<repo>/searches.json          -- map from search query to `CountIssues`
+/// <org>/<repo>/pr-searches.json       -- map from search query to `CountPrs`
+/// ```
+///
+/// Modifications (creating issues, posting comments, ...) are applied to the files,
+/// so running a command twice behaves as it would against github.
+/// The `record-fixtures` command can be used to populate the directory from github.
+pub struct Fixtures {
+    directory: PathBuf,
+
+    /// Held while updating `searches.json` or `pr-searches.json`,
+    /// since searches may be recorded concurrently.
+    searches_lock: Mutex<()>,
+}
+
+impl Fixtures {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            searches_lock: Mutex::new(()),
+        }
+    }
+
+    /// Store `issue` so that it is returned by later calls to `fetch_issue`
+    /// and `list_issues_in_milestone`.
+    pub fn record_issue(&self, repository: &Repository, issue: &ExistingGithubIssue) -> Result<()> {
+        write_json(&self.issue_path(repository, issue.number), issue)
+    }
+
+    pub fn record_labels(&self, repository: &Repository, labels: &[GhLabel]) -> Result<()> {
+        write_json(&self.labels_path(repository), &labels)
+    }
+
+    /// Store the result of `count_issues_matching_search` for `search`.
+    pub fn record_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+        count: &CountIssues,
+    ) -> Result<()> {
+        let _guard = self.searches_lock.lock().unwrap();
+        let path = self.searches_path(repository);
+        let mut searches: BTreeMap<String, CountIssues> = read_searches(&path)?;
+        searches.insert(search.to_string(), count.clone());
+        write_json(&path, &searches)
+    }
+
+    /// Store the result of `count_prs_matching_search` for `search`.
+    pub fn record_pr_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+        count: &CountPrs,
+    ) -> Result<()> {
+        let _guard = self.searches_lock.lock().unwrap();
+        let path = self.pr_searches_path(repository);
+        let mut searches: BTreeMap<String, CountPrs> = read_searches(&path)?;
+        searches.insert(search.to_string(), count.clone());
+        write_json(&path, &searches)
+    }
+
+    fn repository_directory(&self, repository: &Repository) -> PathBuf {
+        self.directory.join(&repository.org).join(&repository.repo)
+    }
+
+    fn issues_directory(&self, repository: &Repository) -> PathBuf {
+        self.repository_directory(repository).join("issues")
+    }
+
+    fn issue_path(&self, repository: &Repository, number: u64) -> PathBuf {
+        self.issues_directory(repository)
+            .join(format!("{number}.json"))
+    }
+
+    fn labels_path(&self, repository: &Repository) -> PathBuf {
+        self.repository_directory(repository).join("labels.json")
+    }
+
+    fn searches_path(&self, repository: &Repository) -> PathBuf {
+        self.repository_directory(repository).join("searches.json")
+    }
+
+    fn pr_searches_path(&self, repository: &Repository) -> PathBuf {
+        self.repository_directory(repository)
+            .join("pr-searches.json")
+    }
+
+    fn all_issues(&self, repository: &Repository) -> Result<Vec<ExistingGithubIssue>> {
+        let directory = self.issues_directory(repository);
+        if !directory.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut issues = vec![];
+        for entry in
+            std::fs::read_dir(&directory).with_path_context(&directory, "reading fixtures")?
+        {
+            let path = entry?.path();
+            if path.extension() == Some("json".as_ref()) {
+                issues.push(read_json::<ExistingGithubIssue>(&path)?);
+            }
+        }
+        issues.sort_by_key(|issue| issue.number);
+        Ok(issues)
+    }
+
+    fn modify_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        op: impl FnOnce(&mut ExistingGithubIssue),
+    ) -> Result<()> {
+        let mut issue = self.fetch_issue(repository, number)?;
+        op(&mut issue);
+        self.record_issue(repository, &issue)
+    }
+}
+
+impl GithubBackend for Fixtures {
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>> {
+        let path = self.labels_path(repository);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        read_json(&path)
+    }
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> Result<()> {
+        let mut labels = self.list_labels(repository)?;
+        labels.retain(|l| l.name != label.name);
+        labels.push(label.clone());
+        self.record_labels(repository, &labels)
+    }
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues> {
+        let mut searches: BTreeMap<String, CountIssues> =
+            read_searches(&self.searches_path(repository))?;
+        match searches.remove(search) {
+            Some(count) => Ok(count),
+            None => {
+                spanned::bail_here!("no fixture recorded for search `{search}` in `{repository}`")
+            }
+        }
+    }
+
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs> {
+        let mut searches: BTreeMap<String, CountPrs> =
+            read_searches(&self.pr_searches_path(repository))?;
+        match searches.remove(search) {
+            Some(count) => Ok(count),
+            None => {
+                spanned::bail_here!(
+                    "no fixture recorded for PR search `{search}` in `{repository}`"
+                )
+            }
+        }
+    }
+
+    fn fetch_issue(&self, repository: &Repository, number: u64) -> Result<ExistingGithubIssue> {
+        let path = self.issue_path(repository, number);
+        if !path.exists() {
+            spanned::bail_here!(
+                "fetching `{}` issue {} failed: no fixture at `{}`",
+                repository,
+                number,
+                path.display()
+            );
+        }
+        read_json(&path)
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>> {
+        let mut issues = self.all_issues(repository)?;
+        issues
+            .retain(|issue| issue.milestone.as_ref().map(|m| m.title.as_str()) == Some(milestone));
+        Ok(issues)
+    }
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> Result<IssueId> {
+        let number = self
+            .all_issues(repository)?
+            .last()
+            .map(|issue| issue.number + 1)
+            .unwrap_or(1);
+
+        let known_labels = self.list_labels(repository)?;
+        let labels = labels
+            .iter()
+            .map(|name| GhLabel {
+                name: name.clone(),
+                color: known_labels
+                    .iter()
+                    .find(|l| l.name == *name)
+                    .map(|l| l.color.clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        self.record_issue(
+            repository,
+            &ExistingGithubIssue {
+                number,
+                title: title.to_string(),
+                assignees: assignees.clone(),
+                comments: vec![],
+                body: body.to_string(),
+                state: GithubIssueState::Open,
+                labels,
+                milestone: Some(GhMilestone {
+                    number: 0,
+                    title: milestone.to_string(),
+                    description: String::new(),
+                    due_on: None,
+                }),
+                updated_at: None,
+            },
+        )?;
+
+        Ok(IssueId::new(repository.clone(), number))
+    }
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str) -> Result<()> {
+        self.modify_issue(repository, number, |issue| issue.title = title.to_string())
+    }
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> Result<()> {
+        self.modify_issue(repository, number, |issue| {
+            issue.milestone = Some(GhMilestone {
+                number: 0,
+                title: milestone.to_string(),
+                description: String::new(),
+                due_on: None,
+            })
+        })
+    }
+
+    fn create_comment(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        let url = IssueId::new(repository.clone(), number).url();
+        self.modify_issue(repository, number, |issue| {
+            issue.comments.push(ExistingGithubComment {
+                author: FIXTURES_AUTHOR.to_string(),
+                body: body.to_string(),
+                created_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                url: format!("{url}#issuecomment-{}", issue.comments.len() + 1),
+                hidden: false,
+            })
+        })
+    }
+
+    fn update_issue_body(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        self.modify_issue(repository, number, |issue| issue.body = body.to_string())
+    }
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> Result<()> {
+        self.modify_issue(repository, number, |issue| {
+            issue.assignees.retain(|a| !remove_owners.contains(a));
+            issue.assignees.extend(add_owners.iter().cloned());
+        })
+    }
+
+    fn sync_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_labels: &BTreeSet<String>,
+        add_labels: &BTreeSet<String>,
+    ) -> Result<()> {
+        let known_labels = self.list_labels(repository)?;
+        self.modify_issue(repository, number, |issue| {
+            issue.labels.retain(|l| !remove_labels.contains(&l.name));
+            for name in add_labels {
+                if !issue.has_label(name) {
+                    issue.labels.push(GhLabel {
+                        name: name.clone(),
+                        color: known_labels
+                            .iter()
+                            .find(|l| l.name == *name)
+                            .map(|l| l.color.clone())
+                            .unwrap_or_default(),
+                    });
+                }
+            }
+        })
+    }
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> Result<()> {
+        // Whether an issue is locked is not recorded; we detect it from the
+        // lock comment, just as with github. Just check the issue exists.
+        self.fetch_issue(repository, number)?;
+        Ok(())
+    }
+
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        _state_reason: Option<IssueStateReason>,
+    ) -> Result<()> {
+        // We don't record the state reason, just as with `gh issue view`.
+        self.modify_issue(repository, number, |issue| {
+            issue.state = GithubIssueState::Closed
+        })
+    }
+}
+
+/// Wraps another backend and saves everything read through it into a [`Fixtures`]
+/// directory. Used by the `record-fixtures` command; modifications are refused
+/// so that recording never changes anything on github.
+pub struct Recorder {
+    inner: Box<dyn GithubBackend>,
+    fixtures: Fixtures,
+}
+
+impl Recorder {
+    pub fn new(inner: Box<dyn GithubBackend>, fixtures: Fixtures) -> Self {
+        Self { inner, fixtures }
+    }
+
+    fn refuse(&self, what: &str) -> Result<()> {
+        spanned::bail_here!("cannot {what} while recording fixtures")
+    }
+}
+
+impl GithubBackend for Recorder {
+    fn sanity_check(&self) -> Result<()> {
+        self.inner.sanity_check()
+    }
+
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>> {
+        let labels = self.inner.list_labels(repository)?;
+        self.fixtures.record_labels(repository, &labels)?;
+        Ok(labels)
+    }
+
+    fn create_label(&self, _repository: &Repository, _label: &GhLabel) -> Result<()> {
+        self.refuse("create labels")
+    }
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues> {
+        let count = self
+            .inner
+            .count_issues_matching_search(repository, search)?;
+        self.fixtures.record_search(repository, search, &count)?;
+        Ok(count)
+    }
+
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs> {
+        let count = self.inner.count_prs_matching_search(repository, search)?;
+        self.fixtures.record_pr_search(repository, search, &count)?;
+        Ok(count)
+    }
+
+    fn fetch_issue(&self, repository: &Repository, number: u64) -> Result<ExistingGithubIssue> {
+        let issue = self.inner.fetch_issue(repository, number)?;
+        self.fixtures.record_issue(repository, &issue)?;
+        Ok(issue)
+    }
+
+    fn fetch_current_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+    ) -> Result<ExistingGithubIssue> {
+        let issue = self.inner.fetch_current_issue(repository, number)?;
+        self.fixtures.record_issue(repository, &issue)?;
+        Ok(issue)
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>> {
+        let issues = self.inner.list_issues_in_milestone(repository, milestone)?;
+        for issue in &issues {
+            self.fixtures.record_issue(repository, issue)?;
+        }
+        Ok(issues)
+    }
+
+    fn create_issue(
+        &self,
+        _repository: &Repository,
+        _body: &str,
+        _title: &str,
+        _labels: &[String],
+        _assignees: &BTreeSet<String>,
+        _milestone: &str,
+    ) -> Result<IssueId> {
+        spanned::bail_here!("cannot create issues while recording fixtures")
+    }
+
+    fn change_title(&self, _repository: &Repository, _number: u64, _title: &str) -> Result<()> {
+        self.refuse("change titles")
+    }
+
+    fn change_milestone(
+        &self,
+        _repository: &Repository,
+        _number: u64,
+        _milestone: &str,
+    ) -> Result<()> {
+        self.refuse("change milestones")
+    }
+
+    fn create_comment(&self, _repository: &Repository, _number: u64, _body: &str) -> Result<()> {
+        self.refuse("post comments")
+    }
+
+    fn update_issue_body(&self, _repository: &Repository, _number: u64, _body: &str) -> Result<()> {
+        self.refuse("update issue bodies")
+    }
+
+    fn sync_assignees(
+        &self,
+        _repository: &Repository,
+        _number: u64,
+        _remove_owners: &BTreeSet<String>,
+        _add_owners: &BTreeSet<String>,
+    ) -> Result<()> {
+        self.refuse("change assignees")
+    }
+
+    fn sync_labels(
+        &self,
+        _repository: &Repository,
+        _number: u64,
+        _remove_labels: &BTreeSet<String>,
+        _add_labels: &BTreeSet<String>,
+    ) -> Result<()> {
+        self.refuse("change labels")
+    }
+
+    fn lock_issue(&self, _repository: &Repository, _number: u64) -> Result<()> {
+        self.refuse("lock issues")
+    }
+
+    fn close_issue(
+        &self,
+        _repository: &Repository,
+        _number: u64,
+        _state_reason: Option<IssueStateReason>,
+    ) -> Result<()> {
+        self.refuse("close issues")
+    }
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path).with_path_context(path, "reading fixture")?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+/// Reads `searches.json` or `pr-searches.json`, which may not exist yet.
+fn read_searches<T: DeserializeOwned>(path: &Path) -> Result<BTreeMap<String, T>> {
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    read_json(path)
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_path_context(parent, "creating fixture directory")?;
+    }
+    let text = serde_json::to_string_pretty(value)?;
+    std::fs::write(path, text).with_path_context(path, "writing fixture")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_round_trip() {
+        let directory = tempfile::tempdir().unwrap();
+        let fixtures = Fixtures::new(directory.path());
+        let repository = Repository::new("rust-lang", "rust-project-goals");
+
+        let issue_id = fixtures
+            .create_issue(
+                &repository,
+                "body",
+                "A goal",
+                &["C-tracking-issue".to_string()],
+                &BTreeSet::from(["alice".to_string()]),
+                "2026",
+            )
+            .unwrap();
+        assert_eq!(issue_id.number, 1);
+
+        fixtures
+            .create_comment(&repository, issue_id.number, "An update")
+            .unwrap();
+        fixtures
+            .change_title(&repository, issue_id.number, "A better goal")
+            .unwrap();
+
+        let issues = fixtures
+            .list_issues_in_milestone(&repository, "2026")
+            .unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].title, "A better goal");
+        assert_eq!(issues[0].comments.len(), 1);
+        assert_eq!(issues[0].comments[0].body, "An update");
+        assert!(issues[0].has_label("C-tracking-issue"));
+
+        assert!(fixtures
+            .list_issues_in_milestone(&repository, "2025h2")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_fixtures_missing_search() {
+        let directory = tempfile::tempdir().unwrap();
+        let fixtures = Fixtures::new(directory.path());
+        let repository = Repository::new("rust-lang", "rust");
+
+        assert!(fixtures
+            .count_issues_matching_search(&repository, "label:C-tracking-issue")
+            .is_err());
+
+        fixtures
+            .record_search(
+                &repository,
+                "label:C-tracking-issue",
+                &CountIssues { open: 2, closed: 3 },
+            )
+            .unwrap();
+        let count = fixtures
+            .count_issues_matching_search(&repository, "label:C-tracking-issue")
+            .unwrap();
+        assert_eq!((count.open, count.closed), (2, 3));
+
+        // PR searches are recorded separately from issue searches.
+        assert!(fixtures
+            .count_prs_matching_search(&repository, "label:C-tracking-issue")
+            .is_err());
+        let prs = CountPrs {
+            open: 1,
+            merged: 4,
+            closed: 2,
+        };
+        fixtures
+            .record_pr_search(&repository, "author:me", &prs)
+            .unwrap();
+        assert_eq!(
+            fixtures
+                .count_prs_matching_search(&repository, "author:me")
+                .unwrap(),
+            prs
+        );
+    }
+}
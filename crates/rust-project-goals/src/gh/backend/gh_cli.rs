@@ -0,0 +1,533 @@
+use std::{collections::BTreeSet, process::Command, time::Duration};
+
+use serde::Deserialize;
+use spanned::{Context, Error, Result};
+
+use crate::gh::{
+    issue_id::{IssueId, Repository},
+    issues::{
+        CountIssues, CountPrs, ExistingGithubIssue, ExistingGithubIssueJson, IssueStateReason,
+    },
+    labels::GhLabel,
+};
+use crate::util::comma;
+use rust_project_goals_json::GithubIssueState;
+
+use super::{GithubBackend, IssueVersion};
+
+/// Most PRs `count_prs_matching_search` will list for a single search.
+const MAX_PRS: u32 = 5000;
+
+/// Talks to github by running the `gh` command-line tool,
+/// which must be installed and configured with a token.
+pub struct GhCli;
+
+impl GithubBackend for GhCli {
+    fn sanity_check(&self) -> Result<()> {
+        if Command::new("gh").arg("--version").output().is_err() {
+            spanned::bail_here!(
+                "The github `gh` client is missing and needs to be installed and configured with a token."
+            );
+        }
+        Ok(())
+    }
+
+    fn pause_between_writes(&self) -> Duration {
+        // `gh` does not retry when we hit github's secondary rate limits.
+        Duration::from_millis(500)
+    }
+
+    fn list_labels(&self, repository: &Repository) -> Result<Vec<GhLabel>> {
+        let mut limit = 128;
+
+        loop {
+            let output = Command::new("gh")
+                .arg("-R")
+                .arg(&repository.to_string())
+                .arg("label")
+                .arg("list")
+                .arg("--json")
+                .arg("name,color")
+                .arg("-L")
+                .arg(format!("{limit}"))
+                .output()?;
+
+            let labels: Vec<GhLabel> = serde_json::from_slice(&output.stdout)?;
+            if labels.len() >= limit {
+                // If we got exactly as many as we asked for,
+                // we might be missing some.
+                limit = limit * 2;
+                continue;
+            }
+
+            return Ok(labels);
+        }
+    }
+
+    fn create_label(&self, repository: &Repository, label: &GhLabel) -> Result<()> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("label")
+            .arg("create")
+            .arg(&label.name)
+            .arg("--color")
+            .arg(&label.color)
+            .arg("--force")
+            .output()?;
+
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to create label `{}`: {}",
+                label.name,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn count_issues_matching_search(
+        &self,
+        repository: &Repository,
+        search: &str,
+    ) -> Result<CountIssues> {
+        #[derive(Deserialize)]
+        struct JustState {
+            state: GithubIssueState,
+        }
+
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("list")
+            .arg("-S")
+            .arg(search)
+            .arg("-s")
+            .arg("all")
+            .arg("--json")
+            .arg("state")
+            .output()?;
+
+        let existing_issues: Vec<JustState> = serde_json::from_slice(&output.stdout)?;
+
+        let mut count_issues = CountIssues { open: 0, closed: 0 };
+
+        for issue in &existing_issues {
+            match issue.state {
+                GithubIssueState::Open => count_issues.open += 1,
+                GithubIssueState::Closed => count_issues.closed += 1,
+            }
+        }
+
+        Ok(count_issues)
+    }
+
+    fn count_prs_matching_search(&self, repository: &Repository, search: &str) -> Result<CountPrs> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "UPPERCASE")]
+        enum PrState {
+            Open,
+            Merged,
+            Closed,
+        }
+
+        #[derive(Deserialize)]
+        struct JustState {
+            state: PrState,
+        }
+
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("pr")
+            .arg("list")
+            .arg("-S")
+            .arg(search)
+            .arg("-s")
+            .arg("all")
+            // `gh` lists only 30 PRs unless told otherwise.
+            .arg("-L")
+            .arg(MAX_PRS.to_string())
+            .arg("--json")
+            .arg("state")
+            .output()?;
+
+        if !output.status.success() {
+            spanned::bail_here!(
+                "searching `{}` for PRs matching `{}` failed: {}",
+                repository,
+                search,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let existing_prs: Vec<JustState> = serde_json::from_slice(&output.stdout)?;
+
+        let mut count_prs = CountPrs {
+            open: 0,
+            merged: 0,
+            closed: 0,
+        };
+
+        for pr in &existing_prs {
+            match pr.state {
+                PrState::Open => count_prs.open += 1,
+                PrState::Merged => count_prs.merged += 1,
+                PrState::Closed => count_prs.closed += 1,
+            }
+        }
+
+        Ok(count_prs)
+    }
+
+    fn fetch_issue(&self, repository: &Repository, issue: u64) -> Result<ExistingGithubIssue> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("view")
+            .arg(&format!("{issue}"))
+            .arg("--json")
+            .arg("title,assignees,number,comments,body,state,labels,milestone,updatedAt")
+            .output()?;
+
+        if !output.status.success() {
+            spanned::bail_here!(
+                "fetching `{}` issue {} failed: {}",
+                repository.to_string(),
+                issue,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let e_i: ExistingGithubIssueJson = serde_json::from_slice(&output.stdout)?;
+
+        Ok(ExistingGithubIssue::from(e_i))
+    }
+
+    fn issue_version(
+        &self,
+        repository: &Repository,
+        number: u64,
+        _etag: Option<&str>,
+    ) -> Result<Option<IssueVersion>> {
+        // Much cheaper than `gh issue view`, which also fetches all the comments.
+        let output = Command::new("gh")
+            .arg("api")
+            .arg(format!(
+                "repos/{}/{}/issues/{number}",
+                repository.org, repository.repo
+            ))
+            .arg("--jq")
+            .arg(".updated_at")
+            .output()
+            .with_str_context("running github cli tool `gh`")?;
+
+        if !output.status.success() {
+            spanned::bail_here!(
+                "fetching `{repository}` issue {number} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(Some(IssueVersion::Current {
+            updated_at: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            etag: None,
+        }))
+    }
+
+    fn list_issues_in_milestone(
+        &self,
+        repository: &Repository,
+        milestone: &str,
+    ) -> Result<Vec<ExistingGithubIssue>> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("list")
+            .arg("-s")
+            .arg("all")
+            .arg("-L")
+            .arg("5000")
+            .arg("-m")
+            .arg(milestone)
+            .arg("--json")
+            .arg("title,assignees,number,comments,body,state,labels,milestone,updatedAt")
+            .output()
+            .with_str_context("running github cli tool `gh`")?;
+
+        let existing_issues: Vec<ExistingGithubIssueJson> = serde_json::from_slice(&output.stdout)?;
+
+        Ok(existing_issues
+            .into_iter()
+            .map(|e_i| ExistingGithubIssue::from(e_i))
+            .collect())
+    }
+
+    fn create_issue(
+        &self,
+        repository: &Repository,
+        body: &str,
+        title: &str,
+        labels: &[String],
+        assignees: &BTreeSet<String>,
+        milestone: &str,
+    ) -> Result<IssueId> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("create")
+            .arg("-b")
+            .arg(&body)
+            .arg("-t")
+            .arg(&title)
+            .arg("-l")
+            .arg(labels.join(","))
+            .arg("-a")
+            .arg(comma(&assignees))
+            .arg("-m")
+            .arg(&milestone)
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::str(format!(
+                "failed to create issue `{}`: {}",
+                title,
+                String::from_utf8_lossy(&output.stderr)
+            )));
+        }
+
+        // Output in stdout looks like
+        //
+        // https://github.com/rust-lang/rust-project-goals/issues/413}
+
+        for line in str::from_utf8(&output.stdout)?.lines() {
+            if let Some(issue_id) = IssueId::from_url(line.trim()) {
+                return Ok(issue_id);
+            }
+        }
+
+        Err(Error::str(format!("creating issue did not return a URL")))
+    }
+
+    fn change_title(&self, repository: &Repository, number: u64, title: &str) -> Result<()> {
+        let mut command = Command::new("gh");
+        command
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("edit")
+            .arg(number.to_string())
+            .arg("-t")
+            .arg(title);
+
+        let output = command.output()?;
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to change milestone `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn change_milestone(
+        &self,
+        repository: &Repository,
+        number: u64,
+        milestone: &str,
+    ) -> Result<()> {
+        let mut command = Command::new("gh");
+        command
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("edit")
+            .arg(number.to_string())
+            .arg("-m")
+            .arg(milestone);
+
+        let output = command.output()?;
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to change milestone `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create_comment(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("comment")
+            .arg(number.to_string())
+            .arg("-b")
+            .arg(body)
+            .output()?;
+
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to leave comment on issue `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update_issue_body(&self, repository: &Repository, number: u64, body: &str) -> Result<()> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("edit")
+            .arg(number.to_string())
+            .arg("-b")
+            .arg(body)
+            .output()?;
+
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to adjust issue body on issue `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn sync_assignees(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_owners: &BTreeSet<String>,
+        add_owners: &BTreeSet<String>,
+    ) -> Result<()> {
+        let mut command = Command::new("gh");
+        command
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("edit")
+            .arg(number.to_string());
+
+        if !remove_owners.is_empty() {
+            command.arg("--remove-assignee").arg(comma(&remove_owners));
+        }
+
+        if !add_owners.is_empty() {
+            command.arg("--add-assignee").arg(comma(&add_owners));
+        }
+
+        let output = command.output()?;
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to sync issue assignees `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn sync_labels(
+        &self,
+        repository: &Repository,
+        number: u64,
+        remove_labels: &BTreeSet<String>,
+        add_labels: &BTreeSet<String>,
+    ) -> Result<()> {
+        let mut command = Command::new("gh");
+        command
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("edit")
+            .arg(number.to_string());
+
+        if !remove_labels.is_empty() {
+            command.arg("--remove-label").arg(comma(&remove_labels));
+        }
+
+        if !add_labels.is_empty() {
+            command.arg("--add-label").arg(comma(&add_labels));
+        }
+
+        let output = command.output()?;
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to sync issue labels `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn lock_issue(&self, repository: &Repository, number: u64) -> Result<()> {
+        let output = Command::new("gh")
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("lock")
+            .arg(number.to_string())
+            .output()?;
+
+        if !output.status.success() {
+            if !output.stderr.starts_with(b"already locked") {
+                return Err(Error::str(format!(
+                    "failed to lock issue `{}`: {}",
+                    number,
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn close_issue(
+        &self,
+        repository: &Repository,
+        number: u64,
+        state_reason: Option<IssueStateReason>,
+    ) -> Result<()> {
+        let mut command = Command::new("gh");
+        command
+            .arg("-R")
+            .arg(&repository.to_string())
+            .arg("issue")
+            .arg("close")
+            .arg(number.to_string());
+
+        if let Some(state_reason) = state_reason {
+            command.arg("--reason").arg(state_reason.to_string());
+        }
+
+        let output = command.output()?;
+        if !output.status.success() {
+            Err(Error::str(format!(
+                "failed to close issue `{}`: {}",
+                number,
+                String::from_utf8_lossy(&output.stderr)
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -1,13 +1,20 @@
-use std::{collections::BTreeSet, fs, process::Command, str::FromStr, time::SystemTime};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
 
 use chrono::NaiveDate;
-use rust_project_goals_json::{GithubIssueState, Progress};
+use rust_project_goals_json::{GithubIssueState, Progress, ProgressSource, ProgressSourceKind};
 use serde::{Deserialize, Serialize};
-use spanned::{Context, Error, Result};
+use spanned::{Error, Result};
 
-use crate::{gh::issue_id::IssueId, re, util::comma};
+use crate::{gh::issue_id::IssueId, re};
 
-use super::{issue_id::Repository, labels::GhLabel, milestone::GhMilestone};
+use super::{backend::backend, issue_id::Repository, labels::GhLabel, milestone::GhMilestone};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ExistingGithubIssue {
@@ -20,20 +27,24 @@ pub struct ExistingGithubIssue {
     pub state: GithubIssueState,
     pub labels: Vec<GhLabel>,
     pub milestone: Option<GhMilestone>,
+    /// When the issue (including its comments) was last updated, if known.
+    /// Used to tell whether a cached copy of the issue is still current.
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ExistingGithubComment {
-    /// Just github username, no `@`
+    /// Github username, with the `@` (e.g., `@alice`)
     pub author: String,
     pub body: String,
     pub created_at: String,
     pub url: String,
-    hidden: bool,
+    pub(super) hidden: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
-struct ExistingGithubIssueJson {
+pub(super) struct ExistingGithubIssueJson {
     title: String,
     number: u64,
     assignees: Vec<ExistingGithubAssigneeJson>,
@@ -42,6 +53,8 @@ struct ExistingGithubIssueJson {
     state: GithubIssueState,
     labels: Vec<GhLabel>,
     milestone: Option<GhMilestone>,
+    #[serde(rename = "updatedAt", default)]
+    updated_at: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -67,161 +80,61 @@ struct ExistingGithubAuthorJson {
     login: String,
 }
 
+/// Why an issue was closed, as recorded by github.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueStateReason {
+    Completed,
+    NotPlanned,
+}
+
+impl std::fmt::Display for IssueStateReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IssueStateReason::Completed => write!(f, "completed"),
+            IssueStateReason::NotPlanned => write!(f, "not planned"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CountIssues {
     pub open: u32,
     pub closed: u32,
 }
 
 pub fn count_issues_matching_search(repository: &Repository, search: &str) -> Result<CountIssues> {
-    #[derive(Deserialize)]
-    struct JustState {
-        state: GithubIssueState,
-    }
+    backend().count_issues_matching_search(repository, search)
+}
 
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("list")
-        .arg("-S")
-        .arg(search)
-        .arg("-s")
-        .arg("all")
-        .arg("--json")
-        .arg("state")
-        .output()?;
-
-    let existing_issues: Vec<JustState> = serde_json::from_slice(&output.stdout)?;
-
-    let mut count_issues = CountIssues { open: 0, closed: 0 };
-
-    for issue in &existing_issues {
-        match issue.state {
-            GithubIssueState::Open => count_issues.open += 1,
-            GithubIssueState::Closed => count_issues.closed += 1,
-        }
-    }
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CountPrs {
+    pub open: u32,
+    pub merged: u32,
+    /// Closed without being merged
+    pub closed: u32,
+}
 
-    Ok(count_issues)
+pub fn count_prs_matching_search(repository: &Repository, search: &str) -> Result<CountPrs> {
+    backend().count_prs_matching_search(repository, search)
 }
 
 pub fn fetch_issue(repository: &Repository, issue: u64) -> Result<ExistingGithubIssue> {
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("view")
-        .arg(&format!("{issue}"))
-        .arg("--json")
-        .arg("title,assignees,number,comments,body,state,labels,milestone")
-        .output()?;
-
-    if !output.status.success() {
-        spanned::bail_here!(
-            "fetching `{}` issue {} failed: {}",
-            repository.to_string(),
-            issue,
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
-
-    let e_i: ExistingGithubIssueJson = serde_json::from_slice(&output.stdout)?;
+    backend().fetch_issue(repository, issue)
+}
 
-    Ok(ExistingGithubIssue::from(e_i))
+/// Fetch the issue from github even if we have a recent copy, for when we are about
+/// to modify it based on what it contains.
+pub fn fetch_current_issue(repository: &Repository, issue: u64) -> Result<ExistingGithubIssue> {
+    backend().fetch_current_issue(repository, issue)
 }
 
+/// All issues (open or closed) in the milestone `timeframe`.
 pub fn list_issues_in_milestone(
     repository: &Repository,
     timeframe: &str,
 ) -> Result<Vec<ExistingGithubIssue>> {
-    list_issues_cached(
-        repository,
-        &[("-m", timeframe)],
-        Some(format!(".issues-{}.json", timeframe)),
-    )
-}
-
-/// Clear the cached issues for a given milestone/timeframe
-pub fn clear_milestone_issues_cache(timeframe: &str) -> Result<()> {
-    let cache_file = format!(".issues-{}.json", timeframe);
-    if std::path::Path::new(&cache_file).exists() {
-        std::fs::remove_file(&cache_file)
-            .with_str_context(format!("Failed to remove cache file {cache_file}"))?;
-    }
-    Ok(())
-}
-
-pub fn list_issues_cached(
-    repository: &Repository,
-    filter: &[(&str, &str)],
-    cache_file: Option<String>,
-) -> Result<Vec<ExistingGithubIssue>> {
-    if let Some(ref cache_path) = cache_file {
-        // Check if cache file exists and is less than 5 minutes old
-        if let Ok(metadata) = fs::metadata(cache_path) {
-            if let Ok(modified) = metadata.modified() {
-                if let Ok(elapsed) = SystemTime::now().duration_since(modified) {
-                    if elapsed.as_secs() < 300 {
-                        // 5 minutes = 300 seconds
-                        // Try to read from cache
-                        if let Ok(cached_data) = fs::read_to_string(cache_path) {
-                            if let Ok(cached_issues) =
-                                serde_json::from_str::<Vec<ExistingGithubIssue>>(&cached_data)
-                            {
-                                return Ok(cached_issues);
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Cache miss or expired - fetch from GitHub
-    let issues = list_issues(repository, filter)?;
-
-    // Save to cache if cache_file is specified
-    if let Some(ref cache_path) = cache_file {
-        if let Ok(serialized) = serde_json::to_string_pretty(&issues) {
-            let _ = fs::write(cache_path, serialized); // Ignore write errors
-        }
-    }
-
-    Ok(issues)
-}
-
-pub fn list_issues(
-    repository: &Repository,
-    filter: &[(&str, &str)],
-) -> Result<Vec<ExistingGithubIssue>> {
-    let mut cmd = Command::new("gh");
-
-    cmd.arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("list")
-        .arg("-s")
-        .arg("all")
-        .arg("-L")
-        .arg("5000");
-
-    for (opt, val) in filter {
-        cmd.arg(opt);
-        cmd.arg(val);
-    }
-
-    let output = cmd
-        .arg("--json")
-        .arg("title,assignees,number,comments,body,state,labels,milestone")
-        .output()
-        .with_str_context("running github cli tool `gh`")?;
-
-    let existing_issues: Vec<ExistingGithubIssueJson> = serde_json::from_slice(&output.stdout)?;
-
-    Ok(existing_issues
-        .into_iter()
-        .map(|e_i| ExistingGithubIssue::from(e_i))
-        .collect())
+    backend().list_issues_in_milestone(repository, timeframe)
 }
 
 pub fn create_issue(
@@ -232,132 +145,23 @@ pub fn create_issue(
     assignees: &BTreeSet<String>,
     milestone: &str,
 ) -> Result<IssueId> {
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("create")
-        .arg("-b")
-        .arg(&body)
-        .arg("-t")
-        .arg(&title)
-        .arg("-l")
-        .arg(labels.join(","))
-        .arg("-a")
-        .arg(comma(&assignees))
-        .arg("-m")
-        .arg(&milestone)
-        .output()?;
-
-    if !output.status.success() {
-        return Err(Error::str(format!(
-            "failed to create issue `{}`: {}",
-            title,
-            String::from_utf8_lossy(&output.stderr)
-        )));
-    }
-
-    // Output in stdout looks like
-    //
-    // https://github.com/rust-lang/rust-project-goals/issues/413}
-
-    for line in str::from_utf8(&output.stdout)?.lines() {
-        if let Some(issue_id) = IssueId::from_url(line.trim()) {
-            return Ok(issue_id);
-        }
-    }
-
-    Err(Error::str(format!("creating issue did not return a URL")))
+    backend().create_issue(repository, body, title, labels, assignees, milestone)
 }
 
 pub fn change_title(repository: &Repository, number: u64, title: &str) -> Result<()> {
-    let mut command = Command::new("gh");
-    command
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("edit")
-        .arg(number.to_string())
-        .arg("-t")
-        .arg(title);
-
-    let output = command.output()?;
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to change milestone `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().change_title(repository, number, title)
 }
 
 pub fn change_milestone(repository: &Repository, number: u64, milestone: &str) -> Result<()> {
-    let mut command = Command::new("gh");
-    command
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("edit")
-        .arg(number.to_string())
-        .arg("-m")
-        .arg(milestone);
-
-    let output = command.output()?;
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to change milestone `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().change_milestone(repository, number, milestone)
 }
 
 pub fn create_comment(repository: &Repository, number: u64, body: &str) -> Result<()> {
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("comment")
-        .arg(number.to_string())
-        .arg("-b")
-        .arg(body)
-        .output()?;
-
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to leave comment on issue `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().create_comment(repository, number, body)
 }
 
 pub fn update_issue_body(repository: &Repository, number: u64, body: &str) -> Result<()> {
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("edit")
-        .arg(number.to_string())
-        .arg("-b")
-        .arg(body)
-        .output()?;
-
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to adjust issue body on issue `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().update_issue_body(repository, number, body)
 }
 
 pub fn sync_assignees(
@@ -366,32 +170,7 @@ pub fn sync_assignees(
     remove_owners: &BTreeSet<String>,
     add_owners: &BTreeSet<String>,
 ) -> Result<()> {
-    let mut command = Command::new("gh");
-    command
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("edit")
-        .arg(number.to_string());
-
-    if !remove_owners.is_empty() {
-        command.arg("--remove-assignee").arg(comma(&remove_owners));
-    }
-
-    if !add_owners.is_empty() {
-        command.arg("--add-assignee").arg(comma(&add_owners));
-    }
-
-    let output = command.output()?;
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to sync issue assignees `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().sync_assignees(repository, number, remove_owners, add_owners)
 }
 
 pub fn sync_labels(
@@ -400,32 +179,7 @@ pub fn sync_labels(
     remove_labels: &BTreeSet<String>,
     add_labels: &BTreeSet<String>,
 ) -> Result<()> {
-    let mut command = Command::new("gh");
-    command
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("edit")
-        .arg(number.to_string());
-
-    if !remove_labels.is_empty() {
-        command.arg("--remove-label").arg(comma(&remove_labels));
-    }
-
-    if !add_labels.is_empty() {
-        command.arg("--add-label").arg(comma(&add_labels));
-    }
-
-    let output = command.output()?;
-    if !output.status.success() {
-        Err(Error::str(format!(
-            "failed to sync issue labels `{}`: {}",
-            number,
-            String::from_utf8_lossy(&output.stderr)
-        )))
-    } else {
-        Ok(())
-    }
+    backend().sync_labels(repository, number, remove_labels, add_labels)
 }
 
 pub const FLAGSHIP_LABEL: &str = "Flagship Goal";
@@ -434,6 +188,14 @@ pub const LOCK_TEXT: &str = "This issue is intended for status updates only.\n\n
 
 pub const CONTINUING_GOAL_PREFIX: &str = "This is a continuing project goal, and the updates below this comment will be for the new period";
 
+pub const CLOSING_GOAL_PREFIX: &str = "Closing this tracking issue because";
+
+/// Start of the reminders posted by `cargo rpg stale` on tracking issues without recent updates.
+pub const STALE_REMINDER_MARKER: &str = "<!-- rust-project-goals:stale-reminder -->";
+
+/// Start of the requests for updates posted by `cargo rpg request-updates`.
+pub const UPDATE_REQUEST_MARKER: &str = "<!-- rust-project-goals:update-request -->";
+
 impl ExistingGithubIssue {
     /// We use the presence of a "lock comment" as a signal that we successfully locked the issue.
     /// The github CLI doesn't let you query that directly.
@@ -453,25 +215,15 @@ impl ExistingGithubIssue {
 }
 
 pub fn lock_issue(repository: &Repository, number: u64) -> Result<()> {
-    let output = Command::new("gh")
-        .arg("-R")
-        .arg(&repository.to_string())
-        .arg("issue")
-        .arg("lock")
-        .arg(number.to_string())
-        .output()?;
-
-    if !output.status.success() {
-        if !output.stderr.starts_with(b"already locked") {
-            return Err(Error::str(format!(
-                "failed to lock issue `{}`: {}",
-                number,
-                String::from_utf8_lossy(&output.stderr)
-            )));
-        }
-    }
+    backend().lock_issue(repository, number)
+}
 
-    Ok(())
+pub fn close_issue(
+    repository: &Repository,
+    number: u64,
+    state_reason: Option<IssueStateReason>,
+) -> Result<()> {
+    backend().close_issue(repository, number, state_reason)
 }
 
 impl ExistingGithubComment {
@@ -487,7 +239,11 @@ impl ExistingGithubComment {
     /// True if this is one of the special comments that we put on issues.
     fn is_automated_comment(&self) -> bool {
         let trimmed_body = self.body.trim();
-        trimmed_body == LOCK_TEXT || trimmed_body.starts_with(CONTINUING_GOAL_PREFIX)
+        trimmed_body == LOCK_TEXT
+            || trimmed_body.starts_with(CONTINUING_GOAL_PREFIX)
+            || trimmed_body.starts_with(CLOSING_GOAL_PREFIX)
+            || trimmed_body.starts_with(STALE_REMINDER_MARKER)
+            || trimmed_body.starts_with(UPDATE_REQUEST_MARKER)
     }
 
     pub fn created_at_date(&self) -> NaiveDate {
@@ -517,17 +273,99 @@ impl From<ExistingGithubIssueJson> for ExistingGithubIssue {
             state: e_i.state,
             labels: e_i.labels,
             milestone: e_i.milestone,
+            updated_at: e_i.updated_at,
         }
     }
 }
 
+/// How many issues [`all_checkboxes`] works on at once, which bounds
+/// the number of requests to github that are in flight.
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// [`checkboxes`] for each of `issues`, in the same order. The issues are processed
+/// concurrently by up to [`MAX_CONCURRENT_REQUESTS`] workers.
+pub fn all_checkboxes<'i>(
+    issues: impl IntoIterator<Item = &'i ExistingGithubIssue>,
+) -> Vec<Progress> {
+    let issues: Vec<&ExistingGithubIssue> = issues.into_iter().collect();
+    let results: Vec<OnceLock<Progress>> = issues.iter().map(|_| OnceLock::new()).collect();
+    let next = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        for _ in 0..MAX_CONCURRENT_REQUESTS.min(issues.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(issue) = issues.get(index) else {
+                    break;
+                };
+                let _ = results[index].set(checkboxes(issue));
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.into_inner().unwrap())
+        .collect()
+}
+
+/// Remembers results for the rest of the run. If several threads ask for the same key
+/// at once, only one of them computes the result while the others wait for it.
+struct Memo<K, V> {
+    entries: Mutex<BTreeMap<K, Arc<OnceLock<std::result::Result<V, String>>>>>,
+}
+
+impl<K: Ord, V: Clone> Memo<K, V> {
+    const fn new() -> Self {
+        Memo {
+            entries: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn get(&self, key: K, op: impl FnOnce() -> Result<V>) -> Result<V> {
+        let entry = self.entries.lock().unwrap().entry(key).or_default().clone();
+        entry
+            .get_or_init(|| op().map_err(|e| e.to_string()))
+            .clone()
+            .map_err(Error::str)
+    }
+}
+
+/// Like [`fetch_issue`], but fetches each issue only once per run. Progress is computed
+/// from the same sub-issues many times (e.g., once for each table that shows it).
+fn fetch_issue_once(repository: &Repository, number: u64) -> Result<ExistingGithubIssue> {
+    static ISSUES: Memo<(Repository, u64), ExistingGithubIssue> = Memo::new();
+    ISSUES.get((repository.clone(), number), || {
+        fetch_issue(repository, number)
+    })
+}
+
+/// Like [`count_issues_matching_search`], but runs each search only once per run.
+fn count_issues_once(repository: &Repository, search: &str) -> Result<CountIssues> {
+    static SEARCHES: Memo<(Repository, String), CountIssues> = Memo::new();
+    SEARCHES.get((repository.clone(), search.to_string()), || {
+        count_issues_matching_search(repository, search)
+    })
+}
+
+/// Like [`count_prs_matching_search`], but runs each search only once per run.
+fn count_prs_once(repository: &Repository, search: &str) -> Result<CountPrs> {
+    static SEARCHES: Memo<(Repository, String), CountPrs> = Memo::new();
+    SEARCHES.get((repository.clone(), search.to_string()), || {
+        count_prs_matching_search(repository, search)
+    })
+}
+
 /// Identify how many sub-items have been completed.
-/// These can be encoded in two different ways:
+/// These can be encoded in several different ways:
 ///
 /// * Option A, the most common, is to have checkboxes in the issue. We just count the number that are checked.
 /// * Option B is to include a metadata line called "Tracked issues" that lists a search query. We count the number of open vs closed issues in that query.
+///   A "Tracked PRs" line works the same way for pull requests, counting merged PRs as done; PRs closed without being merged are not counted.
+/// * Option C is to include a metadata line called "See also" that lists other tracking issues, whose progress we compute in turn.
 ///
-/// Returns a tuple (completed, total) with the number of completed items and the total number of items.
+/// If more than one of these is used (or any of them is given a weight, like `(weight: 2)`),
+/// the result is [`Progress::Weighted`], which keeps the progress of each source.
 pub fn checkboxes(issue: &ExistingGithubIssue) -> Progress {
     match try_checkboxes(&issue) {
         Ok(pair) => pair,
@@ -537,24 +375,94 @@ pub fn checkboxes(issue: &ExistingGithubIssue) -> Progress {
     }
 }
 
+/// How many `See also` links we follow from a tracking issue before giving up.
+pub const MAX_SEE_ALSO_DEPTH: usize = 4;
+
 fn try_checkboxes(issue: &ExistingGithubIssue) -> Result<Progress> {
-    let mut completed = 0;
-    let mut total = 0;
+    try_checkboxes_via(issue, &mut vec![])
+}
+
+/// `path` holds the `See also` issues we followed to get to `issue`, starting below
+/// the tracking issue, so that we can detect cycles.
+fn try_checkboxes_via(issue: &ExistingGithubIssue, path: &mut Vec<IssueId>) -> Result<Progress> {
+    let mut checkboxes = ProgressSource {
+        label: "Checkboxes".to_string(),
+        kind: ProgressSourceKind::Checkboxes,
+        completed: 0,
+        total: 0,
+        weight: None,
+        sources: vec![],
+    };
+
+    // Each source along with the share of its work that is done (if it has any items).
+    let mut sources: Vec<(ProgressSource, Option<f64>)> = vec![];
 
     for line in issue.body.lines() {
         // Does this match TRACKED_ISSUES?
         if let Some(c) = re::TRACKED_ISSUES_QUERY.captures(line) {
             let repo = Repository::from_str(&c["repo"]).map_err(|e| Error::str(e.to_string()))?;
             let query = &c["query"];
+            let (_, weight) = take_weight(line)?;
+
+            let CountIssues { open, closed } = count_issues_once(&repo, query)?;
+            let source = ProgressSource {
+                label: format!("{repo} {query}"),
+                kind: ProgressSourceKind::TrackedIssues {
+                    repository: repo.to_string(),
+                    query: query.to_string(),
+                },
+                completed: closed,
+                total: open + closed,
+                weight,
+                sources: vec![],
+            };
+            let done = share(source.completed, source.total);
+            sources.push((source, done));
+            continue;
+        }
 
-            let CountIssues { open, closed } = count_issues_matching_search(&repo, query)?;
-            completed += closed;
-            total += open + closed;
+        if let Some(c) = re::TRACKED_PRS_QUERY.captures(line) {
+            let repo = Repository::from_str(&c["repo"]).map_err(|e| Error::str(e.to_string()))?;
+            let query = &c["query"];
+            let (_, weight) = take_weight(line)?;
+
+            let CountPrs {
+                open,
+                merged,
+                closed,
+            } = count_prs_once(&repo, query)?;
+            let source = ProgressSource {
+                label: format!("{repo} {query} (PRs)"),
+                kind: ProgressSourceKind::TrackedPrs {
+                    repository: repo.to_string(),
+                    query: query.to_string(),
+                    closed_unmerged: closed,
+                },
+                completed: merged,
+                total: open + merged,
+                weight,
+                sources: vec![],
+            };
+            let done = share(source.completed, source.total);
+            sources.push((source, done));
+            continue;
+        }
+
+        if let Some(c) = re::CHECKBOXES_WEIGHT.captures(line) {
+            let (rest, weight) = take_weight(&c["weight"])?;
+            if weight.is_none() || !rest.trim().is_empty() {
+                spanned::bail_here!(
+                    "expected a weight like `(weight: 2)` in the `Checkboxes` row, found `{}`",
+                    c["weight"].trim()
+                );
+            }
+            checkboxes.weight = weight;
             continue;
         }
 
         if let Some(c) = re::SEE_ALSO_QUERY.captures(line) {
-            let issue_urls = c["issues"].split(&[',', ' ']).filter(|s| !s.is_empty());
+            let (issue_urls, weight) = take_weight(&c["issues"])?;
+            let issue_urls = issue_urls.split(&[',', ' ']).filter(|s| !s.is_empty());
 
             for issue_url in issue_urls {
                 let c = match (
@@ -569,43 +477,297 @@ fn try_checkboxes(issue: &ExistingGithubIssue) -> Result<Progress> {
                 };
                 let repository = Repository::new(&c["org"], &c["repo"]);
                 let issue_number = c["issue"].parse::<u64>()?;
-                let issue = fetch_issue(&repository, issue_number)?;
-                match try_checkboxes(&issue)? {
+                let issue_id = IssueId::new(repository.clone(), issue_number);
+
+                if let Some(start) = path.iter().position(|id| *id == issue_id) {
+                    spanned::bail_here!(
+                        "`See also` references form a cycle: {}",
+                        see_also_path(&path[start..], &issue_id)
+                    );
+                }
+                if path.len() >= MAX_SEE_ALSO_DEPTH {
+                    spanned::bail_here!(
+                        "`See also` references are nested more than {MAX_SEE_ALSO_DEPTH} deep: {}",
+                        see_also_path(path, &issue_id)
+                    );
+                }
+
+                let issue = fetch_issue_once(&repository, issue_number)?;
+                path.push(issue_id);
+                let progress = try_checkboxes_via(&issue, path);
+                path.pop();
+
+                let (completed, total, done, nested) = match progress? {
                     Progress::Binary { is_closed } => {
-                        if is_closed {
-                            completed += 1;
-                        }
-                        total += 1;
+                        (is_closed as u32, 1, Some(is_closed as u32 as f64), vec![])
                     }
 
-                    Progress::Tracked {
-                        completed: c,
-                        total: t,
-                    } => {
-                        completed += c;
-                        total += t;
+                    Progress::Tracked { completed, total } => {
+                        (completed, total, share(completed, total), vec![])
                     }
 
+                    Progress::Weighted {
+                        fraction,
+                        completed,
+                        total,
+                        sources,
+                    } => (completed, total, Some(fraction), sources),
+
                     Progress::Error { message } => {
                         spanned::bail_here!("error parsing {repository}#{issue_number}: {message}")
                     }
-                }
+                };
+                let source = ProgressSource {
+                    label: issue.title.clone(),
+                    kind: ProgressSourceKind::SeeAlso {
+                        repository: repository.to_string(),
+                        number: issue_number,
+                    },
+                    completed,
+                    total,
+                    weight,
+                    sources: nested,
+                };
+                sources.push((source, done));
             }
         }
 
         if re::CHECKED_CHECKBOX.is_match(line) {
-            total += 1;
-            completed += 1;
+            checkboxes.total += 1;
+            checkboxes.completed += 1;
         } else if re::CHECKBOX.is_match(line) {
-            total += 1;
+            checkboxes.total += 1;
         }
     }
 
+    if checkboxes.total > 0 || checkboxes.weight.is_some() {
+        let done = share(checkboxes.completed, checkboxes.total);
+        sources.insert(0, (checkboxes, done));
+    }
+
+    let completed: u32 = sources.iter().map(|(source, _)| source.completed).sum();
+    let total: u32 = sources.iter().map(|(source, _)| source.total).sum();
+
     if total == 0 && completed == 0 {
         Ok(Progress::Binary {
             is_closed: issue.state == GithubIssueState::Closed,
         })
+    } else if sources.len() > 1
+        || sources
+            .iter()
+            .any(|(source, _)| source.weight.is_some() || !source.sources.is_empty())
+    {
+        Ok(Progress::Weighted {
+            fraction: weighted_fraction(&sources),
+            completed,
+            total,
+            sources: sources.into_iter().map(|(source, _)| source).collect(),
+        })
     } else {
         Ok(Progress::Tracked { completed, total })
     }
 }
+
+/// Share of `total` items that are done, if there are any.
+fn share(completed: u32, total: u32) -> Option<f64> {
+    if total > 0 {
+        Some(completed as f64 / total as f64)
+    } else {
+        None
+    }
+}
+
+/// Combines the share done of each source, weighing each by its weight or, if it has none,
+/// by its number of items (so that without weights, every item counts the same).
+/// Sources without items are ignored.
+fn weighted_fraction(sources: &[(ProgressSource, Option<f64>)]) -> f64 {
+    let mut done = 0.0;
+    let mut weights = 0.0;
+    for (source, share) in sources {
+        let Some(share) = share else {
+            continue;
+        };
+        let weight = source.weight.unwrap_or(source.total as f64);
+        done += weight * share;
+        weights += weight;
+    }
+    if weights > 0.0 {
+        done / weights
+    } else {
+        0.0
+    }
+}
+
+/// Removes a weight like `(weight: 2)` from `text`, returning the rest of the text and the weight.
+fn take_weight(text: &str) -> Result<(String, Option<f64>)> {
+    let Some(c) = re::PROGRESS_WEIGHT.captures(text) else {
+        return Ok((text.to_string(), None));
+    };
+    let weight = match c["weight"].trim().parse::<f64>() {
+        Ok(weight) if weight.is_finite() && weight > 0.0 => weight,
+        _ => spanned::bail_here!(
+            "invalid weight `{}`, expected a positive number",
+            c["weight"].trim()
+        ),
+    };
+    Ok((
+        re::PROGRESS_WEIGHT.replace(text, "").into_owned(),
+        Some(weight),
+    ))
+}
+
+/// Formats `path` followed by `last` like `rust-lang/rust#1 -> rust-lang/cargo#2`.
+fn see_also_path(path: &[IssueId], last: &IssueId) -> String {
+    path.iter()
+        .chain([last])
+        .map(|id| format!("{}#{}", id.repository, id.number))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gh::backend::{set_backend, Fixtures};
+
+    #[test]
+    fn test_memo_computes_once() {
+        let memo: Memo<u64, u64> = Memo::new();
+        let calls = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let value = memo.get(1, || {
+                        calls.fetch_add(1, Ordering::Relaxed);
+                        Ok(2)
+                    });
+                    assert_eq!(value.unwrap(), 2);
+                });
+            }
+        });
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        assert!(memo.get(3, || spanned::bail_here!("failed")).is_err());
+        assert!(memo.get(3, || Ok(4)).is_err());
+    }
+
+    #[test]
+    fn test_take_weight() {
+        assert_eq!(
+            take_weight("rust-lang/rust#1 (weight: 2.5)").unwrap(),
+            ("rust-lang/rust#1 ".to_string(), Some(2.5))
+        );
+        assert_eq!(
+            take_weight("rust-lang/rust#1").unwrap(),
+            ("rust-lang/rust#1".to_string(), None)
+        );
+        assert!(take_weight("rust-lang/rust#1 (weight: 0)").is_err());
+        assert!(take_weight("rust-lang/rust#1 (weight: lots)").is_err());
+    }
+
+    #[test]
+    fn test_weighted_fraction() {
+        let source = |completed, total, weight| ProgressSource {
+            label: String::new(),
+            kind: ProgressSourceKind::Checkboxes,
+            completed,
+            total,
+            weight,
+            sources: vec![],
+        };
+
+        // Without weights, every item counts the same.
+        let sources = [
+            (source(1, 4, None), share(1, 4)),
+            (source(4, 4, None), share(4, 4)),
+        ];
+        assert_eq!(weighted_fraction(&sources), 5.0 / 8.0);
+
+        // With weights, each source counts in proportion to its weight,
+        // and sources without items are ignored.
+        let sources = [
+            (source(1, 4, Some(3.0)), share(1, 4)),
+            (source(4, 4, Some(1.0)), share(4, 4)),
+            (source(0, 0, Some(5.0)), share(0, 0)),
+        ];
+        assert_eq!(weighted_fraction(&sources), (3.0 * 0.25 + 1.0) / 4.0);
+    }
+
+    #[test]
+    fn test_tracked_prs() {
+        // This is the only test in the crate that installs a github backend.
+        let directory = tempfile::tempdir().unwrap();
+        let repository = Repository::new("rust-lang", "rust");
+        let fixtures = Fixtures::new(directory.path());
+        let count = CountPrs {
+            open: 1,
+            merged: 3,
+            closed: 2,
+        };
+        fixtures
+            .record_pr_search(&repository, "is:pr label:A-parser", &count)
+            .unwrap();
+        set_backend(Box::new(fixtures)).unwrap();
+
+        let issue = ExistingGithubIssue {
+            number: 1,
+            title: "Faster parsing".to_string(),
+            assignees: BTreeSet::new(),
+            comments: vec![],
+            body: "| Metadata | |\n\
+                   | --- | --- |\n\
+                   | Tracked PRs | [rust-lang/rust is:pr label:A-parser](https://github.com/rust-lang/rust/pulls?q=is%3Apr+label%3AA-parser) |\n\
+                   \n\
+                   - [x] Write the RFC\n"
+                .to_string(),
+            state: GithubIssueState::Open,
+            labels: vec![],
+            milestone: None,
+            updated_at: None,
+        };
+
+        let Progress::Weighted {
+            completed,
+            total,
+            sources,
+            ..
+        } = checkboxes(&issue)
+        else {
+            panic!("expected weighted progress");
+        };
+        assert_eq!((completed, total), (4, 5));
+
+        let [checkbox_source, pr_source] = &sources[..] else {
+            panic!("expected two sources, found {sources:?}");
+        };
+        assert!(matches!(
+            checkbox_source.kind,
+            ProgressSourceKind::Checkboxes
+        ));
+        assert_eq!((checkbox_source.completed, checkbox_source.total), (1, 1));
+
+        // PRs closed without being merged count as neither done nor to do.
+        match &pr_source.kind {
+            ProgressSourceKind::TrackedPrs {
+                repository,
+                query,
+                closed_unmerged,
+            } => {
+                assert_eq!(repository, "rust-lang/rust");
+                assert_eq!(query, "is:pr label:A-parser");
+                assert_eq!(*closed_unmerged, 2);
+            }
+            kind => panic!("expected a `Tracked PRs` source, found {kind:?}"),
+        }
+        assert_eq!((pr_source.completed, pr_source.total), (3, 4));
+    }
+
+    #[test]
+    fn test_see_also_path() {
+        let id = |number| IssueId::new(Repository::new("rust-lang", "rust"), number);
+        assert_eq!(
+            see_also_path(&[id(1), id(2)], &id(1)),
+            "rust-lang/rust#1 -> rust-lang/rust#2 -> rust-lang/rust#1"
+        );
+    }
+}
@@ -1,9 +1,7 @@
-use std::process::Command;
-
 use serde::{Deserialize, Serialize};
 
-use super::issue_id::Repository;
-use spanned::{Error, Result};
+use super::{backend::backend, issue_id::Repository};
+use spanned::Result;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GhLabel {
@@ -13,52 +11,10 @@ pub struct GhLabel {
 
 impl GhLabel {
     pub fn list(repository: &Repository) -> Result<Vec<GhLabel>> {
-        let mut limit = 128;
-
-        loop {
-            let output = Command::new("gh")
-                .arg("-R")
-                .arg(&repository.to_string())
-                .arg("label")
-                .arg("list")
-                .arg("--json")
-                .arg("name,color")
-                .arg("-L")
-                .arg(format!("{limit}"))
-                .output()?;
-
-            let labels: Vec<GhLabel> = serde_json::from_slice(&output.stdout)?;
-            if labels.len() >= limit {
-                // If we got exactly as many as we asked for,
-                // we might be missing some.
-                limit = limit * 2;
-                continue;
-            }
-
-            return Ok(labels);
-        }
+        backend().list_labels(repository)
     }
 
     pub fn create(&self, repository: &Repository) -> Result<()> {
-        let output = Command::new("gh")
-            .arg("-R")
-            .arg(&repository.to_string())
-            .arg("label")
-            .arg("create")
-            .arg(&self.name)
-            .arg("--color")
-            .arg(&self.color)
-            .arg("--force")
-            .output()?;
-
-        if !output.status.success() {
-            Err(Error::str(format!(
-                "failed to create label `{}`: {}",
-                self.name,
-                String::from_utf8_lossy(&output.stderr)
-            )))
-        } else {
-            Ok(())
-        }
+        backend().create_label(repository, self)
     }
 }
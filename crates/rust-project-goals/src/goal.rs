@@ -8,7 +8,7 @@ use spanned::{Error, Result, Spanned};
 
 use crate::config::{Configuration, TeamAskDetails};
 use crate::gh::issue_id::{IssueId, Repository};
-use crate::gh::issues::{checkboxes, ExistingGithubIssue};
+use crate::gh::issues::{all_checkboxes, ExistingGithubIssue};
 use crate::markwaydown::{self, Section, Table};
 use crate::re::{self, CHAMPION_METADATA};
 use crate::team::{self, TeamName};
@@ -123,7 +123,7 @@ pub struct TeamAsk {
 /// This is part of the **new format** introduced in 2026. Instead of listing specific
 /// asks (like "RFC decision"), goals now specify a support level that indicates how
 /// much involvement is needed from each team.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
 pub enum SupportLevel {
     /// Team doesn't need to do anything, but goal author wants to know they like the idea.
     /// Example: Prototyping a new feature on crates.io that you hope to eventually upstream.
@@ -211,6 +211,13 @@ impl TeamInvolvement {
     }
 }
 
+/// Load the goal from a single file, or `None` if the file is not a goal document
+/// (e.g., a README without a metadata table).
+pub fn goal_in_file(path: &Path) -> Result<Option<GoalDocument>> {
+    let link_path = Path::new(path.file_name().unwrap());
+    GoalDocument::load(path, link_path)
+}
+
 /// Load all the goals from a given directory
 pub fn goals_in_dir(directory_path: &Path) -> Result<Vec<GoalDocument>> {
     let mut goal_documents = vec![];
@@ -228,7 +235,9 @@ pub fn goals_in_dir(directory_path: &Path) -> Result<Vec<GoalDocument>> {
 }
 
 impl GoalDocument {
-    fn load(path: &Path, link_path: &Path) -> Result<Option<Self>> {
+    /// Load the goal in `path`, whose path relative to its milestone directory is
+    /// `link_path` (as returned by [`markdown_files`]), or `None` if it is not a goal document.
+    pub fn load(path: &Path, link_path: &Path) -> Result<Option<Self>> {
         let sections = markwaydown::parse(path)?;
 
         let Some(metadata) = extract_metadata(&sections)? else {
@@ -306,6 +315,26 @@ fn generate_progress_html(progress: &Progress, state: &GithubIssueState) -> Stri
         (Progress::Tracked { completed, total }, _) => {
             format!(r#"<progress value="{}" max="{}">{}/{}</progress>"#, completed, total, completed, total)
         },
+        (Progress::Weighted { completed, total, .. }, GithubIssueState::Closed) if completed == total => {
+            r#"<img src="https://img.shields.io/badge/Completed!%20%3D%29-green" alt="Completed">"#.to_string()
+        },
+        (Progress::Weighted { .. }, GithubIssueState::Closed) => {
+            r#"<img src="https://img.shields.io/badge/Will%20not%20complete%20%3A%28-yellow" alt="Incomplete">"#.to_string()
+        },
+        (Progress::Weighted { fraction, sources, .. }, _) => {
+            // Show how far along each source is when hovering over the bar.
+            let breakdown = sources
+                .iter()
+                .map(|source| format!("{}: {}/{}", source.label, source.completed, source.total))
+                .collect::<Vec<_>>()
+                .join("&#10;");
+            format!(
+                r#"<progress value="{:.2}" max="1" title="{}">{:.0}%</progress>"#,
+                fraction,
+                breakdown.replace('"', "&quot;"),
+                fraction * 100.0
+            )
+        },
         (Progress::Binary { is_closed: true }, _) => {
             r#"<img src="https://img.shields.io/badge/Completed!%20%3D%29-green" alt="Completed">"#.to_string()
         },
@@ -331,6 +360,22 @@ pub fn format_goal_table(
     let mut table;
 
     if !show_champions {
+        // Computing the progress may fetch many other issues, so do it for all goals at once.
+        let tracked_issues: Vec<&ExistingGithubIssue> = goals
+            .iter()
+            .filter_map(|goal| goal.metadata.tracking_issue.as_ref())
+            .filter_map(|issue_id| {
+                milestone_issues?
+                    .iter()
+                    .find(|issue| issue.number == issue_id.number)
+            })
+            .collect();
+        let progress: BTreeMap<u64, Progress> = tracked_issues
+            .iter()
+            .map(|issue| issue.number)
+            .zip(all_checkboxes(tracked_issues.iter().copied()))
+            .collect();
+
         table = vec![vec![
             Spanned::here("Goal".to_string()),
             Spanned::here("Point of contact".to_string()),
@@ -359,8 +404,7 @@ pub fn format_goal_table(
                     // Find the matching issue in milestone_issues and generate progress HTML
                     let progress_html = if let Some(issues) = milestone_issues {
                         if let Some(issue) = issues.iter().find(|issue| issue.number == *number) {
-                            let progress = checkboxes(&issue);
-                            generate_progress_html(&progress, &issue.state)
+                            generate_progress_html(&progress[&issue.number], &issue.state)
                         } else {
                             // Issue not found - might be in different milestone or not exist
                             r#"<span title="Issue not found in milestone">⚠️</span>"#.to_string()
@@ -4,8 +4,10 @@ pub mod format_team_ask;
 pub mod format_team_support;
 pub mod gh;
 pub mod goal;
+pub mod lint;
 pub mod markdown_processor;
 pub mod markwaydown;
+pub mod progress_history;
 pub mod re;
 pub mod team;
 pub mod util;
@@ -0,0 +1,609 @@
+//! Lints for goal documents. Unlike the errors reported when loading a goal, lints flag
+//! softer problems, and each one can be allowed, warned about, or denied in the `[lints]`
+//! table of `rust-project-goals.toml`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    ops::Range,
+};
+
+use serde::Deserialize;
+use spanned::Result;
+
+use crate::{
+    config::{Configuration, GoalsConfig},
+    goal::{GoalDocument, SupportLevel, TeamInvolvement},
+    re, team,
+    team::TeamName,
+    util::commas,
+};
+
+/// Summaries longer than this (in characters) make the tracking issue hard to read.
+pub const MAX_SUMMARY_LENGTH: usize = 1500;
+
+/// How a lint is reported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Not reported.
+    Allow,
+    /// Reported, but `cargo rpg check` still succeeds.
+    Warn,
+    /// Reported, and `cargo rpg check` fails.
+    Deny,
+}
+
+impl Display for LintLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LintLevel::Allow => write!(f, "allow"),
+            LintLevel::Warn => write!(f, "warn"),
+            LintLevel::Deny => write!(f, "deny"),
+        }
+    }
+}
+
+pub struct Lint {
+    /// Name of the lint in the `[lints]` table, like `missing-champion`
+    pub id: &'static str,
+
+    pub default_level: LintLevel,
+
+    /// What the lint checks, for the documentation.
+    pub description: &'static str,
+
+    check: fn(&LintContext<'_>) -> Result<Vec<Finding>>,
+}
+
+/// A problem found by a lint, before its level is applied.
+struct Finding {
+    message: String,
+    bytes: Option<Range<usize>>,
+}
+
+/// A problem found by a lint in a goal document.
+#[derive(Debug)]
+pub struct LintFinding {
+    pub lint: &'static str,
+
+    pub level: LintLevel,
+
+    pub message: String,
+
+    /// Where the problem is in the text of the goal document, if we can point at it.
+    pub bytes: Option<Range<usize>>,
+}
+
+struct LintContext<'a> {
+    document: &'a GoalDocument,
+
+    /// Current text of the goal document
+    text: &'a str,
+
+    /// The links configured in `book.toml`
+    goals_config: &'a GoalsConfig,
+}
+
+pub const LINTS: &[Lint] = &[
+    Lint {
+        id: "missing-champion",
+        default_level: LintLevel::Warn,
+        description:
+            "a team that is asked for support has no `[team] champion` row in the metadata \
+            (for 2026 goals, only teams asked for Medium or Large support need a champion)",
+        check: missing_champion,
+    },
+    Lint {
+        id: "invited-point-of-contact",
+        default_level: LintLevel::Warn,
+        description: "an `Invited` goal whose point of contact is not one of its champions \
+            (invited goals have no owner yet, so the point of contact is normally a champion)",
+        check: invited_point_of_contact,
+    },
+    Lint {
+        id: "summary-too-long",
+        default_level: LintLevel::Warn,
+        description: "the summary is too long to read comfortably in the tracking issue",
+        check: summary_too_long,
+    },
+    Lint {
+        id: "missing-zulip-channel",
+        default_level: LintLevel::Warn,
+        description: "the metadata has no `Zulip channel` row",
+        check: missing_zulip_channel,
+    },
+    Lint {
+        id: "unresolved-link",
+        default_level: LintLevel::Warn,
+        description: "a reference-style link like `[text][label]` whose label is not defined \
+            in the document, in `book.toml`, or as a team",
+        check: unresolved_link,
+    },
+    Lint {
+        id: "unknown-team-ask",
+        default_level: LintLevel::Warn,
+        description: "a task that names a team but is not one of the team asks \
+            in `rust-project-goals.toml`, so it is not counted as a team ask",
+        check: unknown_team_ask,
+    },
+];
+
+impl Lint {
+    /// The level configured for this lint, or its default level.
+    pub fn level(&self) -> LintLevel {
+        self.level_in(&Configuration::get().lints)
+    }
+
+    /// The level of this lint in `levels` (a `[lints]` table), or its default level.
+    fn level_in(&self, levels: &BTreeMap<String, LintLevel>) -> LintLevel {
+        levels.get(self.id).copied().unwrap_or(self.default_level)
+    }
+}
+
+/// Checks that the `[lints]` table of `rust-project-goals.toml` only names known lints.
+pub fn check_lint_config() -> Result<()> {
+    check_lint_levels(&Configuration::get().lints)
+}
+
+fn check_lint_levels(levels: &BTreeMap<String, LintLevel>) -> Result<()> {
+    for id in levels.keys() {
+        if !LINTS.iter().any(|lint| lint.id == id) {
+            spanned::bail_here!(
+                "unknown lint `{id}` in `rust-project-goals.toml`, expected one of {}",
+                commas(LINTS.iter().map(|lint| lint.id))
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Runs all lints that are not allowed on `document`, whose current text is `text`.
+/// Goals that were not accepted are not linted.
+pub fn lint_goal(
+    document: &GoalDocument,
+    text: &str,
+    goals_config: &GoalsConfig,
+) -> Result<Vec<LintFinding>> {
+    lint_goal_with_levels(document, text, goals_config, &Configuration::get().lints)
+}
+
+/// Like [`lint_goal`], but with the lint levels in `levels` instead of those configured
+/// in `rust-project-goals.toml`.
+fn lint_goal_with_levels(
+    document: &GoalDocument,
+    text: &str,
+    goals_config: &GoalsConfig,
+    levels: &BTreeMap<String, LintLevel>,
+) -> Result<Vec<LintFinding>> {
+    if !document.is_not_not_accepted() {
+        return Ok(vec![]);
+    }
+
+    let cx = LintContext {
+        document,
+        text,
+        goals_config,
+    };
+
+    let mut findings = vec![];
+    for lint in LINTS {
+        let level = lint.level_in(levels);
+        if level == LintLevel::Allow {
+            continue;
+        }
+
+        for Finding { message, bytes } in (lint.check)(&cx)? {
+            findings.push(LintFinding {
+                lint: lint.id,
+                level,
+                message,
+                bytes,
+            });
+        }
+    }
+    Ok(findings)
+}
+
+fn missing_champion(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let metadata = &cx.document.metadata;
+    let teams: BTreeSet<&'static TeamName> = match &cx.document.team_involvement {
+        TeamInvolvement::Asks(asks) => asks.iter().flat_map(|ask| &ask.teams).copied().collect(),
+        TeamInvolvement::Support(supports) => supports
+            .iter()
+            .filter(|support| support.support_level >= SupportLevel::Medium)
+            .map(|support| support.team)
+            .collect(),
+    };
+
+    Ok(teams
+        .into_iter()
+        .filter(|team| !metadata.champions.contains_key(team))
+        .map(|team| Finding {
+            message: format!("{team} is asked for support, but there is no `{team} champion` row"),
+            bytes: Some(metadata.table.span.bytes.clone()),
+        })
+        .collect())
+}
+
+fn invited_point_of_contact(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let metadata = &cx.document.metadata;
+    if !metadata.status.is_invited
+        || metadata
+            .champions
+            .values()
+            .any(|champion| champion.content.trim() == metadata.pocs.trim())
+    {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![Finding {
+        message: format!(
+            "this goal is invited (looking for an owner), but its point of contact \
+            {pocs} is not a champion; if {pocs} will own the goal, change the status",
+            pocs = metadata.pocs,
+        ),
+        bytes: metadata_value(cx, "Point of contact"),
+    }])
+}
+
+fn summary_too_long(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let length = cx.document.summary.chars().count();
+    if length <= MAX_SUMMARY_LENGTH {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![Finding {
+        message: format!(
+            "the summary is {length} characters long, but it should be at most \
+            {MAX_SUMMARY_LENGTH} since it is copied into the tracking issue; \
+            move the details into the motivation"
+        ),
+        bytes: cx
+            .text
+            .find("## Summary")
+            .map(|start| start..start + "## Summary".len()),
+    }])
+}
+
+fn missing_zulip_channel(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let metadata = &cx.document.metadata;
+    if metadata
+        .table
+        .rows
+        .iter()
+        .any(|row| row[0] == "Zulip channel")
+    {
+        return Ok(vec![]);
+    }
+
+    Ok(vec![Finding {
+        message: "the metadata has no `Zulip channel` row (use `N/A` if there is none)".to_string(),
+        bytes: Some(metadata.table.span.bytes.clone()),
+    }])
+}
+
+fn unresolved_link(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let defined: BTreeSet<String> = re::LINK_DEFINITION
+        .captures_iter(cx.text)
+        .map(|c| c["label"].to_lowercase())
+        .chain(
+            cx.goals_config
+                .links
+                .keys()
+                .map(|label| label.to_lowercase()),
+        )
+        .collect();
+
+    let mut findings = vec![];
+    for (label, bytes) in reference_links(cx.text) {
+        if defined.contains(&label.to_lowercase())
+            || cx
+                .goals_config
+                .linkifiers
+                .iter()
+                .any(|(regex, _)| regex.is_match(&format!("[{label}]")))
+            || team::get_team_name(&label.to_lowercase())?.is_some()
+        {
+            continue;
+        }
+
+        findings.push(Finding {
+            message: format!("no definition for the link `[{label}]`"),
+            bytes: Some(bytes),
+        });
+    }
+    Ok(findings)
+}
+
+fn unknown_team_ask(cx: &LintContext<'_>) -> Result<Vec<Finding>> {
+    let config = Configuration::get();
+
+    let mut findings = vec![];
+    for plan_item in cx
+        .document
+        .goal_plans
+        .iter()
+        .flat_map(|goal_plan| &goal_plan.plan_items)
+    {
+        // Team asks (marked with `![Team][]`) are validated when the goal is loaded.
+        if plan_item.is_team_ask() || config.team_asks.contains_key(&*plan_item.text) {
+            continue;
+        }
+
+        let mut teams = vec![];
+        for captures in re::SHORTCUT_LINK.captures_iter(&plan_item.owners) {
+            if captures.name("next").is_some() {
+                continue;
+            }
+            if let Some(team) = team::get_team_name(&captures["label"].to_lowercase())? {
+                teams.push(team);
+            }
+        }
+        if teams.is_empty() {
+            continue;
+        }
+
+        findings.push(Finding {
+            message: format!(
+                "`{}` names {} but is not one of the team asks in `rust-project-goals.toml`, \
+                so it is not counted as a team ask",
+                *plan_item.text,
+                commas(&teams),
+            ),
+            bytes: Some(plan_item.text.span.bytes.clone()),
+        });
+    }
+    Ok(findings)
+}
+
+/// Location of the value of the metadata row `key`, if there is one.
+fn metadata_value(cx: &LintContext<'_>, key: &str) -> Option<Range<usize>> {
+    cx.document
+        .metadata
+        .table
+        .rows
+        .iter()
+        .find(|row| row[0] == key)
+        .map(|row| row[1].span.bytes.clone())
+}
+
+/// The labels of all full and collapsed reference links in `text` (outside of code blocks)
+/// along with their location.
+fn reference_links(text: &str) -> Vec<(String, Range<usize>)> {
+    let mut links = vec![];
+    let mut in_code_block = false;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            for captures in re::REFERENCE_LINK.captures_iter(line) {
+                let label = match &captures["label"] {
+                    "" => &captures["text"],
+                    label => label,
+                };
+                let m = captures.get(0).unwrap();
+                links.push((label.to_string(), offset + m.start()..offset + m.end()));
+            }
+        }
+        offset += line.len();
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal;
+
+    /// An old-format goal on which no lint fires.
+    const GOAL: &str = r#"# Faster builds
+
+| Metadata            |                      |
+|:--------------------|----------------------|
+| Point of contact    | @alice               |
+| Status              | Proposed             |
+| Zulip channel       | [#t-compiler][zulip] |
+| [compiler] champion | @bob                 |
+
+## Summary
+
+Make builds faster.
+
+## Motivation
+
+Builds are slow, see [the RFC][rfc].
+
+[rfc]: https://github.com/rust-lang/rfcs/pull/1
+[zulip]: https://rust-lang.zulipchat.com/
+
+## Ownership and team asks
+
+| Task             | Owner(s) or team(s)    | Notes |
+|------------------|------------------------|-------|
+| Implementation   | @alice                 |       |
+| Standard reviews | ![Team][] [compiler][] |       |
+"#;
+
+    /// The findings of the lints on the goal document `text`, with the lint levels
+    /// overridden by `levels`.
+    fn lint(text: &str, levels: &[(&str, LintLevel)]) -> Vec<LintFinding> {
+        team::test_support::use_test_checkout();
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("faster-builds.md");
+        std::fs::write(&path, text).unwrap();
+        let document = goal::goal_in_file(&path).unwrap().unwrap();
+
+        let goals_config = GoalsConfig {
+            links: [(
+                "Team".to_string(),
+                "https://img.shields.io/badge/Team%20ask-red".to_string(),
+            )]
+            .into_iter()
+            .collect(),
+            ..GoalsConfig::default()
+        };
+        let levels: BTreeMap<String, LintLevel> = levels
+            .iter()
+            .map(|&(id, level)| (id.to_string(), level))
+            .collect();
+        lint_goal_with_levels(&document, text, &goals_config, &levels).unwrap()
+    }
+
+    /// The ids of the lints reported on the goal document `text` at their default levels.
+    fn lints_found(text: &str) -> Vec<&'static str> {
+        lint(text, &[])
+            .into_iter()
+            .map(|finding| finding.lint)
+            .collect()
+    }
+
+    /// `text` without the lines that start with `prefix`.
+    fn without_line(text: &str, prefix: &str) -> String {
+        text.split_inclusive('\n')
+            .filter(|line| !line.starts_with(prefix))
+            .collect()
+    }
+
+    /// `GOAL` in the 2026 format, asking `[compiler]` and `[lang]` for the given support.
+    fn new_format_goal(compiler: &str, lang: &str) -> String {
+        let (before, _) = GOAL.split_once("## Ownership and team asks").unwrap();
+        format!(
+            "{before}## Team asks\n\n\
+            | Team       | Support level | Notes |\n\
+            |------------|---------------|-------|\n\
+            | [compiler] | {compiler} |       |\n\
+            | [lang]     | {lang} |       |\n"
+        )
+    }
+
+    #[test]
+    fn test_no_lints() {
+        assert_eq!(lints_found(GOAL), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_missing_champion() {
+        let goal = without_line(GOAL, "| [compiler] champion");
+        assert_eq!(lints_found(&goal), vec!["missing-champion"]);
+
+        // In the 2026 format, only Medium and Large support needs a champion.
+        assert_eq!(
+            lints_found(&new_format_goal("Medium", "Small")),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            lints_found(&new_format_goal("Medium", "Large")),
+            vec!["missing-champion"]
+        );
+    }
+
+    #[test]
+    fn test_invited_point_of_contact() {
+        let goal = GOAL.replace("Proposed", "Invited");
+        assert_eq!(lints_found(&goal), vec!["invited-point-of-contact"]);
+
+        let goal = goal.replace("@alice", "@bob");
+        assert_eq!(lints_found(&goal), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_summary_too_long() {
+        let summary = "x".repeat(MAX_SUMMARY_LENGTH + 1);
+        let goal = GOAL.replace("Make builds faster.", &summary);
+        assert_eq!(lints_found(&goal), vec!["summary-too-long"]);
+
+        let goal = GOAL.replace("Make builds faster.", &summary[1..]);
+        assert_eq!(lints_found(&goal), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_missing_zulip_channel() {
+        let goal = without_line(GOAL, "| Zulip channel");
+        assert_eq!(lints_found(&goal), vec!["missing-zulip-channel"]);
+    }
+
+    #[test]
+    fn test_unresolved_link() {
+        let goal = GOAL.replace("[the RFC][rfc]", "[the RFC][rfc-1]");
+        assert_eq!(lints_found(&goal), vec!["unresolved-link"]);
+
+        // Labels are matched case-insensitively, and teams count as defined.
+        let goal = GOAL.replace("[the RFC][rfc]", "[the RFC][RFC] and [lang][]");
+        assert_eq!(lints_found(&goal), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_unknown_team_ask() {
+        let with_task = |task: &str| {
+            GOAL.replace(
+                "| Standard reviews |",
+                &format!("{task}\n| Standard reviews |"),
+            )
+        };
+
+        // Any task that names a team counts, even next to people...
+        for task in [
+            "| Review the design | [lang] | |",
+            "| Review the design | @alice, [lang] | |",
+        ] {
+            assert_eq!(
+                lints_found(&with_task(task)),
+                vec!["unknown-team-ask"],
+                "{task}"
+            );
+        }
+
+        // ...but not configured team asks, links that are not teams, or full reference links.
+        for task in [
+            "| RFC decision | [lang] | |",
+            "| Review the design | [spec] | |",
+            "| Review the design | [lang][] | |",
+        ] {
+            assert_eq!(lints_found(&with_task(task)), Vec::<&str>::new(), "{task}");
+        }
+    }
+
+    #[test]
+    fn test_lint_level_override() {
+        let goal = without_line(GOAL, "| Zulip channel");
+        let found: Vec<(LintLevel, &str)> = lint(&goal, &[])
+            .into_iter()
+            .map(|finding| (finding.level, finding.lint))
+            .collect();
+        assert_eq!(found, vec![(LintLevel::Warn, "missing-zulip-channel")]);
+
+        let found: Vec<(LintLevel, &str)> =
+            lint(&goal, &[("missing-zulip-channel", LintLevel::Deny)])
+                .into_iter()
+                .map(|finding| (finding.level, finding.lint))
+                .collect();
+        assert_eq!(found, vec![(LintLevel::Deny, "missing-zulip-channel")]);
+
+        assert!(lint(&goal, &[("missing-zulip-channel", LintLevel::Allow)]).is_empty());
+    }
+
+    #[test]
+    fn test_check_lint_levels() {
+        let levels: BTreeMap<String, LintLevel> =
+            [("missing-zulip-channel".to_string(), LintLevel::Allow)].into();
+        assert!(check_lint_levels(&levels).is_ok());
+
+        let levels: BTreeMap<String, LintLevel> =
+            [("missing-champions".to_string(), LintLevel::Allow)].into();
+        assert!(check_lint_levels(&levels).is_err());
+    }
+
+    #[test]
+    fn test_reference_links() {
+        let text = "See [the RFC][rfc] and ![Help wanted][].\n```\n[not][a link]\n```\n[x](y)\n";
+        assert_eq!(
+            reference_links(text),
+            vec![
+                ("rfc".to_string(), 4..18),
+                ("Help wanted".to_string(), 24..39)
+            ]
+        );
+    }
+}
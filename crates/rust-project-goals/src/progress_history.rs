@@ -0,0 +1,211 @@
+//! Dated snapshots of the progress of each tracking issue in a milestone, so that we can
+//! show how goals moved over the period. The snapshots are recorded by
+//! `cargo rpg snapshot-progress` into [`HISTORY_FILE`] in the milestone directory
+//! and rendered as burn-up charts by the `(((BURN-UP CHART)))` placeholder.
+
+use std::path::{Path, PathBuf};
+
+use rust_project_goals_json::Progress;
+use serde::{Deserialize, Serialize};
+use spanned::{Context as _, Error, Result};
+
+/// Name of the file in the milestone directory (e.g., `src/2025h2`) that holds the snapshots.
+pub const HISTORY_FILE: &str = "progress-history.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProgressHistory {
+    /// Snapshots in chronological order, at most one per day
+    pub snapshots: Vec<ProgressSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProgressSnapshot {
+    /// Day the snapshot was taken, as `YYYY-MM-DD`
+    pub date: String,
+    pub issues: Vec<IssueSnapshot>,
+}
+
+/// The state of one tracking issue at the time of a snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueSnapshot {
+    pub number: u64,
+    pub title: String,
+    pub progress: Progress,
+
+    /// Number of comments on the issue, including automated ones
+    pub comments: usize,
+}
+
+impl ProgressHistory {
+    pub fn path(milestone_directory: &Path) -> PathBuf {
+        milestone_directory.join(HISTORY_FILE)
+    }
+
+    /// Loads the history of the milestone in `milestone_directory`,
+    /// which is empty if no snapshot was recorded yet.
+    pub fn load(milestone_directory: &Path) -> Result<Self> {
+        let path = Self::path(milestone_directory);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text =
+            std::fs::read_to_string(&path).with_path_context(&path, "reading progress history")?;
+        serde_json::from_str(&text)
+            .map_err(|e| Error::str(format!("failed to parse {}: {e}", path.display())))
+    }
+
+    pub fn save(&self, milestone_directory: &Path) -> Result<PathBuf> {
+        let path = Self::path(milestone_directory);
+        std::fs::write(&path, serde_json::to_string_pretty(self)? + "\n")
+            .with_path_context(&path, "writing progress history")?;
+        Ok(path)
+    }
+
+    /// Adds `snapshot`, replacing any earlier snapshot from the same day
+    /// so that recording twice in a day keeps only the latest.
+    pub fn record(&mut self, snapshot: ProgressSnapshot) {
+        self.snapshots.retain(|s| s.date != snapshot.date);
+        self.snapshots.push(snapshot);
+        self.snapshots.sort_by(|a, b| a.date.cmp(&b.date));
+    }
+
+    /// A mermaid burn-up chart of the completed and total items over time, for the tracking
+    /// issue `number` or, if `None`, summed over all tracking issues. `None` if there are
+    /// no snapshots (of that issue).
+    ///
+    /// Issues tracked by open/closed state count as one item,
+    /// and issues whose progress could not be computed are left out.
+    pub fn burn_up_chart(&self, title: &str, number: Option<u64>) -> Option<String> {
+        let mut dates = vec![];
+        let mut completed = vec![];
+        let mut total = vec![];
+        for snapshot in &self.snapshots {
+            let items: Vec<(u32, u32)> = snapshot
+                .issues
+                .iter()
+                .filter(|issue| match number {
+                    Some(number) => issue.number == number,
+                    None => true,
+                })
+                .filter_map(|issue| counts(&issue.progress))
+                .collect();
+            if items.is_empty() {
+                continue;
+            }
+            dates.push(format!("\"{}\"", snapshot.date));
+            completed.push(items.iter().map(|(c, _)| c).sum::<u32>().to_string());
+            total.push(items.iter().map(|(_, t)| t).sum::<u32>().to_string());
+        }
+
+        if dates.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            "```mermaid\n\
+            xychart-beta\n    \
+            title \"{}\"\n    \
+            x-axis [{}]\n    \
+            y-axis \"Items\"\n    \
+            line [{}]\n    \
+            line [{}]\n\
+            ```\n",
+            title.replace('"', "'"),
+            dates.join(", "),
+            total.join(", "),
+            completed.join(", "),
+        ))
+    }
+}
+
+/// The completed and total items of `progress`, if it could be computed.
+fn counts(progress: &Progress) -> Option<(u32, u32)> {
+    match *progress {
+        Progress::Binary { is_closed } => Some((is_closed as u32, 1)),
+        Progress::Tracked { completed, total }
+        | Progress::Weighted {
+            completed, total, ..
+        } => Some((completed, total)),
+        Progress::Error { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(date: &str, progress: Vec<(u64, Progress)>) -> ProgressSnapshot {
+        ProgressSnapshot {
+            date: date.to_string(),
+            issues: progress
+                .into_iter()
+                .map(|(number, progress)| IssueSnapshot {
+                    number,
+                    title: String::new(),
+                    progress,
+                    comments: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_burn_up_chart() {
+        let mut history = ProgressHistory::default();
+        history.record(snapshot(
+            "2025-10-01",
+            vec![
+                (
+                    1,
+                    Progress::Tracked {
+                        completed: 1,
+                        total: 4,
+                    },
+                ),
+                (2, Progress::Binary { is_closed: false }),
+            ],
+        ));
+        history.record(snapshot(
+            "2025-09-01",
+            vec![(
+                1,
+                Progress::Tracked {
+                    completed: 0,
+                    total: 3,
+                },
+            )],
+        ));
+        // Recording again on the same day replaces the earlier snapshot.
+        history.record(snapshot(
+            "2025-10-01",
+            vec![
+                (
+                    1,
+                    Progress::Tracked {
+                        completed: 2,
+                        total: 4,
+                    },
+                ),
+                (2, Progress::Binary { is_closed: true }),
+                (
+                    3,
+                    Progress::Error {
+                        message: String::new(),
+                    },
+                ),
+            ],
+        ));
+
+        assert_eq!(
+            history.burn_up_chart("2025h2", None).unwrap(),
+            "```mermaid\nxychart-beta\n    title \"2025h2\"\n    \
+            x-axis [\"2025-09-01\", \"2025-10-01\"]\n    y-axis \"Items\"\n    \
+            line [3, 5]\n    line [0, 3]\n```\n"
+        );
+        assert!(history
+            .burn_up_chart("Goal", Some(2))
+            .unwrap()
+            .contains("x-axis [\"2025-10-01\"]"));
+        assert_eq!(history.burn_up_chart("Goal", Some(3)), None);
+    }
+}
@@ -86,11 +86,29 @@ lazy_static! {
             .unwrap();
 }
 
+lazy_static! {
+    pub static ref TRACKED_PRS_QUERY: Regex =
+        Regex::new(r"^\| *Tracked +PRs *\| *\[(?P<repo>[^ ]*) (?P<query>[^]]*)\]\(.*\) *\| *$")
+            .unwrap();
+}
+
 lazy_static! {
     pub static ref SEE_ALSO_QUERY: Regex =
         Regex::new(r"^\| *See also *\|(?P<issues>[^|]+)\| *$").unwrap();
 }
 
+lazy_static! {
+    /// The weight of a `Tracked issues` or `See also` row, like `(weight: 2)`
+    pub static ref PROGRESS_WEIGHT: Regex =
+        Regex::new(r"\(weight: *(?P<weight>[^)]*)\)").unwrap();
+}
+
+lazy_static! {
+    /// A `| Checkboxes | (weight: 2) |` row giving the weight of the checkboxes in the issue
+    pub static ref CHECKBOXES_WEIGHT: Regex =
+        Regex::new(r"^\| *Checkboxes *\|(?P<weight>[^|]+)\| *$").unwrap();
+}
+
 lazy_static! {
     pub static ref SEE_ALSO_ISSUE1: Regex =
         Regex::new(r"(?P<org>[^#/]*)/(?P<repo>[^#/]*)#(?P<issue>[0-9]+)").unwrap();
@@ -132,6 +150,49 @@ lazy_static! {
             .unwrap();
 }
 
+lazy_static! {
+    /// Burn-up chart placeholder, for the whole milestone or for one goal (named by its file)
+    pub static ref BURN_UP_CHART: Regex =
+        Regex::new(r"\(\(\(BURN-UP CHART(?::\s*([^)]+?))?\s*\)\)\)")
+            .unwrap();
+}
+
+lazy_static! {
+    /// Placeholder for the card of a goal (named by its file), as in the monthly updates
+    pub static ref GOAL_CARD: Regex =
+        Regex::new(r"\(\(\(GOAL CARD:\s*([^)]+?)\s*\)\)\)")
+            .unwrap();
+}
+
+lazy_static! {
+    /// Placeholder for the recent updates on a goal (named by its file)
+    pub static ref GOAL_COMMENTS: Regex =
+        Regex::new(r"\(\(\(GOAL COMMENTS:\s*([^)]+?)\s*\)\)\)")
+            .unwrap();
+}
+
+lazy_static! {
+    /// A full or collapsed reference-style link (or image) like `[text][label]` or `[text][]`
+    pub static ref REFERENCE_LINK: Regex =
+        Regex::new(r"\[(?P<text>[^\[\]]+)\]\[(?P<label>[^\[\]]*)\]")
+            .unwrap();
+}
+
+lazy_static! {
+    /// A link reference definition like `[label]: https://...`
+    pub static ref LINK_DEFINITION: Regex =
+        Regex::new(r"(?m)^ {0,3}\[(?P<label>[^\[\]]+)\]:")
+            .unwrap();
+}
+
+lazy_static! {
+    /// A shortcut link like `[lang]`, as used to name teams; `next` is set if it is actually
+    /// the start of a full link like `[lang][]` or `[lang](...)`
+    pub static ref SHORTCUT_LINK: Regex =
+        Regex::new(r"\[(?P<label>[-.A-Za-z]+)\](?P<next>[\[(])?")
+            .unwrap();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +216,38 @@ mod tests {
         let caps = REPORTS.captures("(((REPORTS: 2025-09-01)))").unwrap();
         assert_eq!(caps.get(1).unwrap().as_str(), "2025-09-01");
     }
+
+    #[test]
+    fn test_goal_card_regex() {
+        assert!(!GOAL_CARD.is_match("(((GOAL CARD)))"));
+
+        let caps = GOAL_CARD
+            .captures("(((GOAL CARD: 2025h2/cargo-plumbing.md )))")
+            .unwrap();
+        assert_eq!(caps.get(1).unwrap().as_str(), "2025h2/cargo-plumbing.md");
+    }
+
+    #[test]
+    fn test_burn_up_chart_regex() {
+        let caps = BURN_UP_CHART.captures("(((BURN-UP CHART)))").unwrap();
+        assert!(caps.get(1).is_none());
+
+        let caps = BURN_UP_CHART
+            .captures("(((BURN-UP CHART: cargo-plumbing.md )))")
+            .unwrap();
+        assert_eq!(caps.get(1).unwrap().as_str(), "cargo-plumbing.md");
+    }
+
+    #[test]
+    fn test_tracked_prs_regex() {
+        let caps = TRACKED_PRS_QUERY
+            .captures("| Tracked PRs | [rust-lang/rust is:pr label:A-parser](https://github.com/rust-lang/rust/pulls?q=is%3Apr+label%3AA-parser) |")
+            .unwrap();
+        assert_eq!(&caps["repo"], "rust-lang/rust");
+        assert_eq!(&caps["query"], "is:pr label:A-parser");
+
+        assert!(!TRACKED_PRS_QUERY.is_match(
+            "| Tracked issues | [rust-lang/rust label:C-tracking-issue](https://github.com/rust-lang/rust/issues?q=label%3AC-tracking-issue) |"
+        ));
+    }
 }
@@ -1,10 +1,87 @@
-use std::{collections::BTreeMap, sync::OnceLock};
+//! Data about Rust teams and people, from the [team repository](https://github.com/rust-lang/team).
+//!
+//! The data is downloaded from the team API and kept as a snapshot on disk
+//! (see [`TeamDataOptions`]), so that it is not downloaded on every run and
+//! so that we can work offline once a snapshot exists. Alternatively, it can be
+//! read from a local checkout of the team repository.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
 
 use rust_team_data::v1;
 use serde::de::DeserializeOwned;
 
-use crate::util::in_thread;
-use spanned::{Error, Result};
+use crate::{config::TeamDataConfig, util::in_thread};
+use spanned::{Context as _, Error, Result};
+
+mod checkout;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+
+/// If this environment variable is set (to anything but `0`) and no options were configured
+/// explicitly, team data is only read from the snapshot and never downloaded.
+/// This is how the mdbook preprocessor can be run offline.
+pub const OFFLINE_ENV_VAR: &str = "RUST_PROJECT_GOALS_OFFLINE";
+
+/// If this environment variable is set and no options were configured explicitly,
+/// it names a checkout of the team repository to read the team data from.
+pub const TEAM_CHECKOUT_ENV_VAR: &str = "RUST_PROJECT_GOALS_TEAM_CHECKOUT";
+
+/// Where team data is kept on disk and when it is downloaded again.
+#[derive(Clone, Debug)]
+pub struct TeamDataOptions {
+    /// Directory with the snapshot of the team data (`people.json` and `teams.json`).
+    pub snapshot_dir: PathBuf,
+
+    /// A snapshot older than this is downloaded again (unless we are offline).
+    pub ttl: Duration,
+
+    /// If true, only read the snapshot and never download anything.
+    pub offline: bool,
+
+    /// If set, read `people/*.toml` and `teams/*.toml` from this checkout of the
+    /// team repository instead of using the snapshot or the team API.
+    pub team_checkout: Option<PathBuf>,
+}
+
+impl TeamDataOptions {
+    /// The options from the `[team_data]` table of the `rust-project-goals.toml` in the
+    /// current directory (or the defaults, if there is none), offline if [`OFFLINE_ENV_VAR`]
+    /// is set, and reading from the checkout named by [`TEAM_CHECKOUT_ENV_VAR`] if it is set.
+    pub fn from_environment() -> Result<Self> {
+        let config = TeamDataConfig::from_working_directory()?;
+        Ok(TeamDataOptions {
+            snapshot_dir: config.snapshot_dir,
+            ttl: Duration::from_secs(config.ttl_hours * 60 * 60),
+            offline: std::env::var_os(OFFLINE_ENV_VAR).is_some_and(|value| value != "0"),
+            team_checkout: std::env::var_os(TEAM_CHECKOUT_ENV_VAR).map(PathBuf::from),
+        })
+    }
+}
+
+static OPTIONS: OnceLock<TeamDataOptions> = OnceLock::new();
+
+/// Select how team data is loaded.
+/// This must be called before any team data is used.
+pub fn set_options(options: TeamDataOptions) -> Result<()> {
+    OPTIONS
+        .set(options)
+        .map_err(|_| Error::str("team data options were already initialized".to_string()))
+}
+
+/// Returns the options for loading team data. If [`set_options`] was not called,
+/// these are [`TeamDataOptions::from_environment`].
+pub fn options() -> Result<&'static TeamDataOptions> {
+    if let Some(options) = OPTIONS.get() {
+        return Ok(options);
+    }
+    let options = TeamDataOptions::from_environment()?;
+    Ok(OPTIONS.get_or_init(|| options))
+}
 
 trait Load<T> {
     fn load(&self, op: impl FnOnce() -> Result<T>) -> Result<&T>;
@@ -14,7 +91,7 @@ impl<T> Load<T> for OnceLock<Result<T>> {
     fn load(&self, op: impl FnOnce() -> Result<T>) -> Result<&T> {
         match self.get_or_init(op) {
             Ok(data) => Ok(data),
-            Err(e) => Err(Error::str(format!("failed to fetch: {e:?}"))),
+            Err(e) => Err(Error::str(format!("failed to load team data: {e:?}"))),
         }
     }
 }
@@ -31,7 +108,10 @@ pub struct PersonData {
 pub fn get_person_data(username: &str) -> Result<Option<&'static PersonData>> {
     static DATA: OnceLock<Result<BTreeMap<String, PersonData>>> = OnceLock::new();
     let people = DATA.load(|| {
-        let data: v1::People = fetch("people.json")?;
+        let data: v1::People = match &options()?.team_checkout {
+            Some(checkout) => checkout::load_people(checkout)?,
+            None => fetch("people.json")?,
+        };
         Ok(data
             .people
             .into_iter()
@@ -50,50 +130,80 @@ pub fn get_person_data(username: &str) -> Result<Option<&'static PersonData>> {
     Ok(people.get(&username[1..].to_lowercase()))
 }
 
-#[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
-pub struct TeamName(String);
+/// A team from the team data, which can only be obtained by looking it up
+/// (see [`get_team_name`]) and so always comes with the data of the team.
+#[derive(Debug)]
+pub struct TeamName {
+    name: String,
+    data: v1::Team,
+}
+
+impl PartialEq for TeamName {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for TeamName {}
+
+impl PartialOrd for TeamName {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TeamName {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.name.cmp(&other.name)
+    }
+}
 
 impl std::fmt::Display for TeamName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}]", self.0)
+        write!(f, "[{}]", self.name)
     }
 }
 
 pub fn get_team_names() -> Result<impl Iterator<Item = &'static TeamName>> {
-    Ok(get_teams()?.keys())
+    Ok(get_teams()?.values())
 }
 
-fn get_teams() -> Result<&'static BTreeMap<TeamName, v1::Team>> {
-    static DATA: OnceLock<Result<BTreeMap<TeamName, v1::Team>>> = OnceLock::new();
+fn get_teams() -> Result<&'static BTreeMap<String, TeamName>> {
+    static DATA: OnceLock<Result<BTreeMap<String, TeamName>>> = OnceLock::new();
     DATA.load(|| {
-        let teams: v1::Teams = fetch("teams.json")?;
+        let teams: v1::Teams = match &options()?.team_checkout {
+            Some(checkout) => checkout::load_teams(checkout)?,
+            None => fetch("teams.json")?,
+        };
         Ok(teams
             .teams
             .into_iter()
-            .map(|(team_name, value)| (TeamName(team_name.to_lowercase()), value))
+            .map(|(team_name, data)| {
+                let name = team_name.to_lowercase();
+                (name.clone(), TeamName { name, data })
+            })
             .collect())
     })
 }
 
 pub fn get_team_name(team_name: &str) -> Result<Option<&'static TeamName>> {
-    let team_name = TeamName(team_name.to_string());
-    Ok(get_teams()?.get_key_value(&team_name).map(|(key, _)| key))
+    Ok(get_teams()?.get(team_name))
 }
 
 impl TeamName {
     /// Get the data for this team.
-    pub fn data(&self) -> &'static v1::Team {
-        get_teams().unwrap().get(self).unwrap()
+    pub fn data(&self) -> &v1::Team {
+        &self.data
     }
 
     /// Name in braces (markdown link), like `"[compiler][]"`
     pub fn name(&self) -> String {
-        format!("[{}][]", self.0)
+        format!("[{}][]", self.name)
     }
 
     /// Name and link, like `"[compiler](https://...)"`
     pub fn name_and_link(&self) -> String {
-        format!("[{}]({})", self.0, self.url())
+        format!("[{}]({})", self.name, self.url())
     }
 
     pub fn url(&self) -> String {
@@ -109,27 +219,106 @@ impl TeamName {
 
     /// Label to use on github
     pub fn gh_label(&self) -> String {
-        format!("T-{}", self.0)
+        format!("T-{}", self.name)
     }
 }
 
+/// Downloads the team data again and replaces the snapshot, regardless of its age.
+/// Returns the files that were written.
+pub fn refresh_snapshot() -> Result<Vec<PathBuf>> {
+    let options = options()?;
+    if options.offline {
+        spanned::bail_here!("cannot refresh the team data snapshot while offline");
+    }
+
+    Ok(vec![
+        refresh_file::<v1::People>(&options.snapshot_dir, "people.json")?,
+        refresh_file::<v1::Teams>(&options.snapshot_dir, "teams.json")?,
+    ])
+}
+
+fn refresh_file<T: DeserializeOwned>(snapshot_dir: &Path, path: &str) -> Result<PathBuf> {
+    let snapshot = snapshot_dir.join(path);
+    let json = download(path)?;
+    // Check that the data is usable before replacing a snapshot that is.
+    parse::<T>(&snapshot, &json)?;
+    write_snapshot(&snapshot, &json)?;
+    Ok(snapshot)
+}
+
+/// Loads `path` (e.g., `people.json`) from the snapshot if it is fresh enough (or we are
+/// offline), and otherwise from the team API, updating the snapshot. If the download fails,
+/// we fall back to a stale snapshot.
 fn fetch<T>(path: &str) -> Result<T>
 where
     T: DeserializeOwned + Send,
 {
+    let options = options()?;
+    let snapshot = options.snapshot_dir.join(path);
+
+    if options.offline {
+        let Ok(json) = std::fs::read_to_string(&snapshot) else {
+            spanned::bail_here!(
+                "no snapshot of the team data at {} while offline; \
+                run `cargo rpg team-data refresh` while online first",
+                snapshot.display()
+            );
+        };
+        return parse(&snapshot, &json);
+    }
+
+    let is_fresh = std::fs::metadata(&snapshot)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < options.ttl);
+    if is_fresh {
+        let json =
+            std::fs::read_to_string(&snapshot).with_path_context(&snapshot, "reading team data")?;
+        return parse(&snapshot, &json);
+    }
+
+    let downloaded = download(path).and_then(|json| Ok((parse(&snapshot, &json)?, json)));
+    match downloaded {
+        Ok((data, json)) => {
+            if let Err(e) = write_snapshot(&snapshot, &json) {
+                eprintln!("warning: failed to save the team data snapshot: {e:?}");
+            }
+            Ok(data)
+        }
+        Err(e) => {
+            let Ok(json) = std::fs::read_to_string(&snapshot) else {
+                return Err(e);
+            };
+            eprintln!(
+                "warning: failed to download {path}, using the older snapshot at {}: {e:?}",
+                snapshot.display()
+            );
+            parse(&snapshot, &json)
+        }
+    }
+}
+
+/// Downloads `path` from the team API.
+fn download(path: &str) -> Result<String> {
     // Run this on another thread because it can create a tokio runtime
     // for the block reqwest API which makes tokio grouchy when that runtime is
     // dropped.
     in_thread(|| {
         let url = format!("{}/{}", v1::BASE_URL, path);
-        let json_response = reqwest::blocking::get(&url)?.json().map_err(|e| {
-            use std::error::Error;
+        Ok(reqwest::blocking::get(&url)?.error_for_status()?.text()?)
+    })
+}
 
-            e.source()
-                .map(|json_error| spanned::Error::str(json_error.to_string()))
-                .unwrap_or(e.into())
-        })?;
+/// Parses team data that was read from (or will be written to) `snapshot`.
+fn parse<T: DeserializeOwned>(snapshot: &Path, json: &str) -> Result<T> {
+    serde_json::from_str(json)
+        .map_err(|e| Error::str(format!("failed to parse {}: {e}", snapshot.display())))
+}
 
-        Ok(json_response)
-    })
+fn write_snapshot(snapshot: &Path, json: &str) -> Result<()> {
+    let dir = snapshot.parent().unwrap();
+    std::fs::create_dir_all(dir).with_path_context(dir, "creating team data directory")?;
+    std::fs::write(snapshot, json).with_path_context(snapshot, "writing team data")?;
+    Ok(())
 }
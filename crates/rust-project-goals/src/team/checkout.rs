@@ -0,0 +1,267 @@
+//! Reading team data from a local checkout of the [team repository](https://github.com/rust-lang/team)
+//! instead of from the team API, so that changes to teams or people can be checked
+//! against the goals before they are merged.
+//!
+//! We read `people/*.toml` and `teams/*.toml` and convert them into the same JSON the
+//! API serves (see `cargo run static-api` in the team repository). Only the fields we
+//! need are filled in, and teams only include their direct members (not the members
+//! of `included-teams`).
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use rust_team_data::v1;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use spanned::{Context as _, Error, Result};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PersonToml {
+    name: String,
+    github: String,
+    github_id: u64,
+    /// A string, or `false` for people who do not want an email address listed
+    email: Option<toml::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TeamToml {
+    name: String,
+    kind: Option<String>,
+    subteam_of: Option<String>,
+    top_level: Option<bool>,
+    people: TeamPeopleToml,
+    website: Option<WebsiteToml>,
+}
+
+#[derive(Deserialize)]
+struct TeamPeopleToml {
+    #[serde(default)]
+    leads: Vec<String>,
+    #[serde(default)]
+    members: Vec<MemberToml>,
+    #[serde(default)]
+    alumni: Vec<MemberToml>,
+}
+
+/// Members are listed either by github username or as a table with their roles.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MemberToml {
+    Github(String),
+    Detailed {
+        github: String,
+        #[serde(default)]
+        roles: Vec<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct WebsiteToml {
+    name: String,
+    description: String,
+    page: Option<String>,
+    email: Option<String>,
+    repo: Option<String>,
+    zulip_stream: Option<String>,
+    matrix_room: Option<String>,
+    #[serde(default)]
+    weight: i64,
+}
+
+/// Loads `people/*.toml` from the team repository checked out at `checkout`.
+pub(super) fn load_people(checkout: &Path) -> Result<v1::People> {
+    let people: serde_json::Map<String, serde_json::Value> = read_people(checkout)?
+        .into_values()
+        .map(|person| {
+            let email = match person.email {
+                Some(toml::Value::String(email)) => Some(email),
+                _ => None,
+            };
+            (
+                person.github,
+                json!({
+                    "name": person.name,
+                    "email": email,
+                    "github_id": person.github_id,
+                }),
+            )
+        })
+        .collect();
+    convert(checkout, json!({ "people": people }))
+}
+
+/// Loads `teams/*.toml` from the team repository checked out at `checkout`.
+/// Fails if a team lists someone who has no file in `people/`.
+pub(super) fn load_teams(checkout: &Path) -> Result<v1::Teams> {
+    let people = read_people(checkout)?;
+
+    let mut teams = serde_json::Map::new();
+    for path in toml_files(&checkout.join("teams"))? {
+        let team: TeamToml = read_toml(&path)?;
+
+        let member = |member: &MemberToml, is_lead: bool| -> Result<serde_json::Value> {
+            let (github, roles): (&String, &[String]) = match member {
+                MemberToml::Github(github) => (github, &[]),
+                MemberToml::Detailed { github, roles } => (github, roles),
+            };
+            let Some(person) = people.get(&github.to_lowercase()) else {
+                spanned::bail_here!(
+                    "team `{}` in {} lists `{github}`, who has no file in `people/`",
+                    team.name,
+                    path.display()
+                );
+            };
+            Ok(json!({
+                "name": person.name,
+                "github": person.github,
+                "github_id": person.github_id,
+                "is_lead": is_lead,
+                "roles": roles,
+            }))
+        };
+
+        let members = team
+            .people
+            .members
+            .iter()
+            .map(|m| member(m, team.people.leads.iter().any(|lead| lead == github_of(m))))
+            .collect::<Result<Vec<_>>>()?;
+        let alumni = team
+            .people
+            .alumni
+            .iter()
+            .map(|m| member(m, false))
+            .collect::<Result<Vec<_>>>()?;
+
+        let website_data = team.website.map(|website| {
+            json!({
+                "name": website.name,
+                "description": website.description,
+                "page": website.page.unwrap_or_else(|| team.name.clone()),
+                "email": website.email,
+                "repo": website.repo,
+                "discord": null,
+                "zulip_stream": website.zulip_stream,
+                "matrix_room": website.matrix_room,
+                "weight": website.weight,
+            })
+        });
+
+        teams.insert(
+            team.name.clone(),
+            json!({
+                "name": team.name,
+                "kind": team.kind.as_deref().unwrap_or("team"),
+                "subteam_of": team.subteam_of,
+                "top_level": team.top_level,
+                "members": members,
+                "alumni": alumni,
+                "github": null,
+                "website_data": website_data,
+                "roles": [],
+                "discord": [],
+            }),
+        );
+    }
+    convert(checkout, json!({ "teams": teams }))
+}
+
+fn github_of(member: &MemberToml) -> &str {
+    match member {
+        MemberToml::Github(github) | MemberToml::Detailed { github, .. } => github,
+    }
+}
+
+/// The people in the checkout, keyed by their lowercased github username.
+fn read_people(checkout: &Path) -> Result<BTreeMap<String, PersonToml>> {
+    let mut people = BTreeMap::new();
+    for path in toml_files(&checkout.join("people"))? {
+        let person: PersonToml = read_toml(&path)?;
+        people.insert(person.github.to_lowercase(), person);
+    }
+    Ok(people)
+}
+
+/// The `.toml` files directly in `dir`, sorted by name.
+fn toml_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir).with_path_context(dir, "reading team repository")? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = std::fs::read_to_string(path).with_path_context(path, "reading team data")?;
+    toml::from_str(&text)
+        .map_err(|e| Error::str(format!("failed to parse {}: {e}", path.display())))
+}
+
+fn convert<T: DeserializeOwned>(checkout: &Path, value: serde_json::Value) -> Result<T> {
+    serde_json::from_value(value).map_err(|e| {
+        Error::str(format!(
+            "failed to convert the team data in {}: {e}",
+            checkout.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::test_support::write_file;
+
+    #[test]
+    fn test_load_checkout() {
+        let checkout = tempfile::tempdir().unwrap();
+        let write = |path: &str, text: &str| write_file(checkout.path(), path, text);
+        write(
+            "people/alice.toml",
+            "name = \"Alice\"\ngithub = \"Alice\"\ngithub-id = 1\nemail = false\n",
+        );
+        write(
+            "people/bob.toml",
+            "name = \"Bob\"\ngithub = \"bob\"\ngithub-id = 2\n",
+        );
+        write(
+            "teams/compiler.toml",
+            "name = \"compiler\"\n\n\
+            [people]\nleads = [\"Alice\"]\nmembers = [\"Alice\", { github = \"bob\", roles = [] }]\n\n\
+            [website]\nname = \"Compiler team\"\ndescription = \"Compiling\"\n\
+            repo = \"https://github.com/rust-lang/compiler-team\"\n",
+        );
+
+        let people = load_people(checkout.path()).unwrap();
+        assert_eq!(people.people["Alice"].name, "Alice");
+        assert_eq!(people.people["bob"].name, "Bob");
+
+        let teams = load_teams(checkout.path()).unwrap();
+        let compiler = &teams.teams["compiler"];
+        assert_eq!(compiler.members.len(), 2);
+        assert!(compiler.members[0].is_lead);
+        assert!(!compiler.members[1].is_lead);
+        assert_eq!(
+            compiler.website_data.as_ref().unwrap().repo.as_deref(),
+            Some("https://github.com/rust-lang/compiler-team")
+        );
+
+        write(
+            "teams/lang.toml",
+            "name = \"lang\"\n\n[people]\nleads = []\nmembers = [\"carol\"]\n",
+        );
+        assert!(load_teams(checkout.path()).is_err());
+    }
+}
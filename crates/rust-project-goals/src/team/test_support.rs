@@ -0,0 +1,45 @@
+//! Team data for tests, read from a small checkout of the team repository
+//! that is written to a temporary directory.
+
+use std::{path::Path, sync::OnceLock, time::Duration};
+
+use super::TeamDataOptions;
+
+/// Writes `text` to the file `path` (relative to `checkout`), creating its directory.
+pub fn write_file(checkout: &Path, path: &str, text: &str) {
+    let path = checkout.join(path);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, text).unwrap();
+}
+
+/// Reads the team data from a checkout with the `compiler` and `lang` teams,
+/// whose only member is `@alice`.
+///
+/// The checkout is written the first time this is called and kept until the
+/// process exits, so every test in the process can call this.
+pub fn use_test_checkout() {
+    static CHECKOUT: OnceLock<tempfile::TempDir> = OnceLock::new();
+    CHECKOUT.get_or_init(|| {
+        let checkout = tempfile::tempdir().unwrap();
+        write_file(
+            checkout.path(),
+            "people/alice.toml",
+            "name = \"Alice\"\ngithub = \"alice\"\ngithub-id = 1\n",
+        );
+        for team in ["compiler", "lang"] {
+            write_file(
+                checkout.path(),
+                &format!("teams/{team}.toml"),
+                &format!("name = \"{team}\"\n\n[people]\nleads = []\nmembers = [\"alice\"]\n"),
+            );
+        }
+        super::set_options(TeamDataOptions {
+            snapshot_dir: checkout.path().join("snapshot"),
+            ttl: Duration::ZERO,
+            offline: true,
+            team_checkout: Some(checkout.path().to_path_buf()),
+        })
+        .unwrap();
+        checkout
+    });
+}